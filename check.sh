@@ -31,6 +31,21 @@ echo
 echo "PRETTIER"
 prettier --write .
 
+# Generate code fragments for include_code_sample
+echo
+echo "ANCHORS"
+cargo run --bin anchors
+
+# Generate the table of contents
+echo
+echo "TABLE OF CONTENTS"
+cargo run --bin toc
+
+# Check local links and assets
+echo
+echo "LINKS"
+cargo run --bin links
+
 # Run checks
 echo
 echo "ZOLA"
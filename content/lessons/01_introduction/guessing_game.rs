@@ -0,0 +1,216 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    fn parse(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    // The secret number is between 1 and this (inclusive).
+    fn max_number(self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Medium => 100,
+            Difficulty::Hard => 1000,
+        }
+    }
+
+    // Enough to always win with binary search on easy and medium, but not on hard.
+    fn attempts(self) -> u32 {
+        match self {
+            Difficulty::Easy => 4,
+            Difficulty::Medium => 7,
+            Difficulty::Hard => 8,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Won { attempts: u32 },
+    Lost,
+    // The input ended before the game did.
+    Quit,
+}
+
+fn secret_number(rng: &mut impl Rng, difficulty: Difficulty) -> u32 {
+    rng.gen_range(1..=difficulty.max_number())
+}
+
+// Generic over the input and output, so that the tests can play a whole game
+// without a keyboard - the real game uses stdin and stdout.
+fn play(
+    secret: u32,
+    difficulty: Difficulty,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<Outcome> {
+    let max = difficulty.max_number();
+    let attempts = difficulty.attempts();
+    writeln!(output, "I'm thinking of a number between 1 and {max}.")?;
+    writeln!(output, "You have {attempts} attempts to guess it.")?;
+
+    let mut attempt = 1;
+    while attempt <= attempts {
+        write!(output, "Attempt {attempt}/{attempts}, your guess: ")?;
+        // Without flushing, the prompt could stay in the buffer while we wait for the input.
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            writeln!(output, "Bye! The number was {secret}.")?;
+            return Ok(Outcome::Quit);
+        }
+
+        // Invalid input doesn't cost an attempt.
+        let guess: u32 = match line.trim().parse() {
+            Ok(guess) => guess,
+            Err(error) => {
+                writeln!(output, "`{}` is not a valid number ({error}).", line.trim())?;
+                continue;
+            }
+        };
+        if !(1..=max).contains(&guess) {
+            writeln!(output, "The number is between 1 and {max}.")?;
+            continue;
+        }
+
+        match guess.cmp(&secret) {
+            Ordering::Less => writeln!(output, "Too small!")?,
+            Ordering::Greater => writeln!(output, "Too big!")?,
+            Ordering::Equal => {
+                writeln!(output, "You win after {attempt} attempt(s)!")?;
+                return Ok(Outcome::Won { attempts: attempt });
+            }
+        }
+        attempt += 1;
+    }
+
+    writeln!(output, "You lose! The number was {secret}.")?;
+    Ok(Outcome::Lost)
+}
+
+// Set the `LESSONS_SEED` environment variable to always get the same number.
+fn rng() -> StdRng {
+    match std::env::var("LESSONS_SEED") {
+        Ok(seed) => StdRng::seed_from_u64(seed.parse().expect("LESSONS_SEED must be a number")),
+        Err(_) => StdRng::from_entropy(),
+    }
+}
+
+fn main() {
+    let difficulty = match std::env::args().nth(1) {
+        None => Difficulty::Medium,
+        Some(name) => Difficulty::parse(&name).unwrap_or_else(|| {
+            eprintln!("Usage: guessing_game [easy | medium | hard]");
+            std::process::exit(1);
+        }),
+    };
+
+    let secret = secret_number(&mut rng(), difficulty);
+    play(secret, difficulty, io::stdin().lock(), io::stdout()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Plays a game with the given input, returning its outcome and everything that was printed.
+    fn play_script(secret: u32, difficulty: Difficulty, input: &str) -> (Outcome, String) {
+        let mut output = Vec::new();
+        let outcome = play(secret, difficulty, input.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn secret_number_is_in_range() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            for _ in 0..1000 {
+                let secret = secret_number(&mut rng, difficulty);
+                assert!((1..=difficulty.max_number()).contains(&secret));
+            }
+        }
+    }
+
+    #[test]
+    fn whole_session_with_fixed_seed() {
+        let secret = secret_number(&mut StdRng::seed_from_u64(42), Difficulty::Medium);
+        assert_eq!(secret, 14);
+
+        // Binary search never needs more than 7 attempts for 100 numbers.
+        let (outcome, output) =
+            play_script(secret, Difficulty::Medium, "50\n25\n12\n18\n15\n13\n14\n");
+        assert_eq!(outcome, Outcome::Won { attempts: 7 });
+        assert_eq!(
+            output,
+            "\
+I'm thinking of a number between 1 and 100.
+You have 7 attempts to guess it.
+Attempt 1/7, your guess: Too big!
+Attempt 2/7, your guess: Too big!
+Attempt 3/7, your guess: Too small!
+Attempt 4/7, your guess: Too big!
+Attempt 5/7, your guess: Too big!
+Attempt 6/7, your guess: Too small!
+Attempt 7/7, your guess: You win after 7 attempt(s)!
+"
+        );
+    }
+
+    #[test]
+    fn invalid_input_is_reported_and_retried() {
+        let (outcome, output) = play_script(7, Difficulty::Easy, "seven\n\n-1\n0\n11\n7\n");
+        assert_eq!(outcome, Outcome::Won { attempts: 1 });
+        assert_eq!(
+            output,
+            "\
+I'm thinking of a number between 1 and 10.
+You have 4 attempts to guess it.
+Attempt 1/4, your guess: `seven` is not a valid number (invalid digit found in string).
+Attempt 1/4, your guess: `` is not a valid number (cannot parse integer from empty string).
+Attempt 1/4, your guess: `-1` is not a valid number (invalid digit found in string).
+Attempt 1/4, your guess: The number is between 1 and 10.
+Attempt 1/4, your guess: The number is between 1 and 10.
+Attempt 1/4, your guess: You win after 1 attempt(s)!
+"
+        );
+    }
+
+    #[test]
+    fn attempts_are_bounded() {
+        let (outcome, output) = play_script(10, Difficulty::Easy, "1\n2\n3\n4\n10\n");
+        assert_eq!(outcome, Outcome::Lost);
+        assert!(output.ends_with("Too small!\nYou lose! The number was 10.\n"));
+        assert!(!output.contains("Attempt 5/4"));
+    }
+
+    #[test]
+    fn end_of_input_quits() {
+        let (outcome, output) = play_script(3, Difficulty::Hard, "500\n");
+        assert_eq!(outcome, Outcome::Quit);
+        assert!(output.ends_with("Too big!\nAttempt 2/8, your guess: \nBye! The number was 3.\n"));
+    }
+
+    #[test]
+    fn difficulty_is_parsed() {
+        assert_eq!(Difficulty::parse("easy"), Some(Difficulty::Easy));
+        assert_eq!(Difficulty::parse("hard"), Some(Difficulty::Hard));
+        assert_eq!(Difficulty::parse("impossible"), None);
+    }
+}
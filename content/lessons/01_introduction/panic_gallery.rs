@@ -0,0 +1,115 @@
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+// Each of these compiles just fine, but fails at runtime.
+
+fn index_out_of_bounds() {
+    let array = ["hello", "new", "world!"];
+    // Off by one: `..=` includes `array.len()`.
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..=array.len() {
+        println!("{}", array[i]);
+    }
+}
+
+fn division_by_zero() {
+    // The compiler can't tell that this is always 0 (Clippy can, though).
+    let joy = if false { 1 } else { 0 };
+    #[allow(clippy::erasing_op)]
+    let joy_division = 0 / joy;
+    println!("{}", joy_division);
+}
+
+fn add_one(x: u8) -> u8 {
+    x + 1
+}
+
+fn overflow() {
+    // Overflow is checked in debug builds only, release builds wrap around.
+    println!("{}", add_one(255));
+}
+
+fn unwrap() {
+    let number: i32 = "forty-two".parse().unwrap();
+    println!("{}", number);
+}
+
+const FAILURES: [(&str, fn()); 4] = [
+    ("index-out-of-bounds", index_out_of_bounds),
+    ("division-by-zero", division_by_zero),
+    ("overflow", overflow),
+    ("unwrap", unwrap),
+];
+
+// Runs the function, catching the panic instead of letting it end the program.
+// Returns the panic's message and location, if it panicked.
+fn catch(failure: fn()) -> Option<(String, String)> {
+    // The location is known only to the panic hook, which by default prints it to stderr.
+    let location = Arc::new(Mutex::new(String::new()));
+    let hook_location = Arc::clone(&location);
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Some(location) = info.location() {
+            *hook_location.lock().unwrap() = location.to_string();
+        }
+    }));
+    let result = panic::catch_unwind(failure);
+    panic::set_hook(default_hook);
+
+    let payload = result.err()?;
+    // `panic!` with a message made by formatting gives a `String`, otherwise a `&str`.
+    let message = match payload.downcast_ref::<String>() {
+        Some(message) => message.clone(),
+        None => payload.downcast_ref::<&str>().unwrap_or(&"").to_string(),
+    };
+    let location = location.lock().unwrap().clone();
+    Some((message, location))
+}
+
+fn show(name: &str, failure: fn()) {
+    println!("=== {name}");
+    match catch(failure) {
+        Some((message, location)) => println!("panicked at {location}:\n{message}"),
+        None => println!("no panic"),
+    }
+}
+
+fn show_all() {
+    for (name, failure) in FAILURES {
+        show(name, failure);
+    }
+}
+
+fn main() {
+    // These, on the other hand, don't even compile:
+
+    // println("hello world"); // doesn't compile (E0423): `println` is a macro, not a function
+
+    // An unsuccessful attempt at emulating C's ability to read memory we're not supposed to access
+    // (error: format argument must be a string literal)
+    // let format = "a very innocent hello {}";
+    // println!(format);
+
+    // The compiler notices some runtime errors while generating code
+    // (error: this operation will panic at runtime)
+    // let array = ["hello", "new", "world!"];
+    // println!("{}", array[3]);
+
+    // let joy_division = 0 / 0; // (error: this operation will panic at runtime)
+
+    let Some(name) = std::env::args().nth(1) else {
+        show_all();
+        return;
+    };
+    match FAILURES
+        .iter()
+        .find(|(failure_name, _)| *failure_name == name)
+    {
+        Some((name, failure)) => show(name, *failure),
+        None => {
+            let names: Vec<&str> = FAILURES.iter().map(|(name, _)| *name).collect();
+            eprintln!("Usage: panic_gallery [{}]", names.join(" | "));
+            std::process::exit(1);
+        }
+    }
+}
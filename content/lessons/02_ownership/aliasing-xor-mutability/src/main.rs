@@ -1,6 +1,9 @@
-fn next_int() -> i32 {
-    42
-}
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{mpsc, Mutex, RwLock};
+use std::thread;
+
+// How many values the writer writes, and how many times the reader reads.
+const ITERATIONS: i32 = 1000;
 
 struct Data(i32);
 impl Data {
@@ -8,32 +11,217 @@ impl Data {
         Self(0)
     }
 
-    fn read(&self) -> i32 { self.0 }
+    fn read(&self) -> i32 {
+        self.0
+    }
 
-    fn write(&mut self, n: i32) { self.0 = n }
+    fn write(&mut self, n: i32) {
+        self.0 = n
+    }
 }
 
-fn thread1(shared_data: &mut Data) {
-    loop {
-        shared_data.write(next_int());
-    }
+// One thread keeps writing to the data while the other keeps reading it:
+// `thread1` borrows `shared_data` mutably while `thread2` borrows it immutably, at the same time.
+// That's a data race, and the borrow checker doesn't let it compile (E0502):
+// fn broken() {
+//     fn thread1(shared_data: &mut Data) {
+//         loop {
+//             shared_data.write(42);
+//         }
+//     }
+//     fn thread2(shared_data: &Data) {
+//         loop {
+//             println!("{}", shared_data.read());
+//         }
+//     }
+//     let mut shared_data = Data::new();
+//     std::thread::scope(|s| {
+//         s.spawn(|| thread1(&mut shared_data));
+//         s.spawn(|| thread2(&shared_data));
+//     });
+// }
+
+// Below are the ways to fix it. In each of them, the writer writes 1, 2, ..., ITERATIONS,
+// and the reader reads the data ITERATIONS times. The writer yields after each write,
+// so that the reader has a chance to see some of the values in between.
+
+// What the reader saw.
+struct Report {
+    reads: Vec<i32>,
+    // The data after both threads finished.
+    last: i32,
 }
 
-fn thread2(shared_data: &Data) {
-    loop {
-        println!("{}", shared_data.read());
+impl Report {
+    // The reader may miss some writes, but it can't see a value that was never written,
+    // nor an older value after a newer one.
+    fn never_decreased(&self) -> bool {
+        self.reads.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    fn is_consistent(&self) -> bool {
+        self.never_decreased()
+            && self.reads.iter().all(|n| (0..=ITERATIONS).contains(n))
+            && self.last == ITERATIONS
     }
 }
 
-fn main() {
-    let mut shared_data = Data::new();
+// Only one thread at a time can access the data.
+fn with_mutex() -> Report {
+    let shared_data = Mutex::new(Data::new());
+    let reads = thread::scope(|s| {
+        s.spawn(|| {
+            for n in 1..=ITERATIONS {
+                shared_data.lock().unwrap().write(n);
+                thread::yield_now();
+            }
+        });
+        let reader = s.spawn(|| {
+            (0..ITERATIONS)
+                .map(|_| shared_data.lock().unwrap().read())
+                .collect()
+        });
+        reader.join().unwrap()
+    });
+    let last = shared_data.into_inner().unwrap().read();
+    Report { reads, last }
+}
+
+// Many readers or one writer at a time - aliasing XOR mutability, checked at runtime.
+fn with_rwlock() -> Report {
+    let shared_data = RwLock::new(Data::new());
+    let reads = thread::scope(|s| {
+        s.spawn(|| {
+            for n in 1..=ITERATIONS {
+                shared_data.write().unwrap().write(n);
+                thread::yield_now();
+            }
+        });
+        let reader = s.spawn(|| {
+            (0..ITERATIONS)
+                .map(|_| shared_data.read().unwrap().read())
+                .collect()
+        });
+        reader.join().unwrap()
+    });
+    let last = shared_data.into_inner().unwrap().read();
+    Report { reads, last }
+}
 
-    std::thread::scope(|s| {
-        let t1 = s.spawn(|| {
-            thread1(&mut shared_data);
+// An atomic integer can be modified through a shared reference, no lock needed.
+fn with_atomic() -> Report {
+    let shared_data = AtomicI32::new(0);
+    let reads = thread::scope(|s| {
+        s.spawn(|| {
+            for n in 1..=ITERATIONS {
+                shared_data.store(n, Ordering::Relaxed);
+                thread::yield_now();
+            }
         });
-        let t2 = s.spawn(|| {
-            thread2(&shared_data);
+        let reader = s.spawn(|| {
+            (0..ITERATIONS)
+                .map(|_| shared_data.load(Ordering::Relaxed))
+                .collect()
         });
+        reader.join().unwrap()
     });
-}
\ No newline at end of file
+    let last = shared_data.into_inner();
+    Report { reads, last }
+}
+
+// Nothing is shared: the writer owns the data and sends copies of it to the reader.
+fn with_channel() -> Report {
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|s| {
+        let writer = s.spawn(move || {
+            let mut data = Data::new();
+            for n in 1..=ITERATIONS {
+                data.write(n);
+                sender.send(data.read()).unwrap();
+            }
+            data.read()
+        });
+        // The reader gets every value, after the sender is dropped the iterator ends.
+        let reader = s.spawn(move || receiver.iter().collect());
+        Report {
+            reads: reader.join().unwrap(),
+            last: writer.join().unwrap(),
+        }
+    })
+}
+
+type Version = fn() -> Report;
+
+const VERSIONS: [(&str, Version); 4] = [
+    ("--mutex", with_mutex),
+    ("--rwlock", with_rwlock),
+    ("--atomic", with_atomic),
+    ("--channel", with_channel),
+];
+
+fn run(flag: &str, version: Version) {
+    let report = version();
+    let distinct = {
+        let mut reads = report.reads.clone();
+        reads.dedup();
+        reads.len()
+    };
+    println!(
+        "{flag}: {} reads, {distinct} distinct values, never decreased: {}, final value: {} => {}",
+        report.reads.len(),
+        report.never_decreased(),
+        report.last,
+        if report.is_consistent() {
+            "consistent"
+        } else {
+            "INCONSISTENT"
+        }
+    );
+}
+
+fn main() {
+    // Without a flag, compare all versions.
+    let Some(flag) = std::env::args().nth(1) else {
+        for (flag, version) in VERSIONS {
+            run(flag, version);
+        }
+        return;
+    };
+    match VERSIONS.iter().find(|(name, _)| *name == flag) {
+        Some((flag, version)) => run(flag, *version),
+        None => {
+            let flags: Vec<&str> = VERSIONS.iter().map(|(flag, _)| *flag).collect();
+            eprintln!("Usage: aliasing_xor_mutability [{}]", flags.join(" | "));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_version_is_consistent() {
+        for (flag, version) in VERSIONS {
+            let report = version();
+            assert!(report.is_consistent(), "{flag}: {:?}", report.reads);
+            assert_eq!(report.reads.len(), ITERATIONS as usize);
+        }
+    }
+
+    #[test]
+    fn channel_delivers_every_value_in_order() {
+        let report = with_channel();
+        assert_eq!(report.reads, (1..=ITERATIONS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stale_reads_are_detected() {
+        let report = Report {
+            reads: vec![1, 3, 2],
+            last: ITERATIONS,
+        };
+        assert!(!report.is_consistent());
+    }
+}
@@ -0,0 +1,230 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{self, PanicHookInfo};
+use std::path::Path;
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+// Prints the line and remembers it, so that the tests can check what happened, and in which order.
+fn say(line: String) {
+    println!("{line}");
+    LOG.with(|log| log.borrow_mut().push(line));
+}
+
+struct LoudInt(i32);
+
+impl Drop for LoudInt {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            say(format!("[{}] Farewell, unwinding!", self.0));
+        } else {
+            say(format!("[{}] Farewell!", self.0));
+        }
+    }
+}
+
+// `panic!` with a message made by formatting gives a `String`, otherwise a `&str`.
+fn message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<String>() {
+        Some(message) => message,
+        None => payload
+            .downcast_ref::<&str>()
+            .copied()
+            .unwrap_or("<unknown>"),
+    }
+}
+
+// Called by every panic, before the unwinding starts.
+// The default hook prints "thread 'main' panicked at ..." to stderr.
+fn reporter(info: &PanicHookInfo) {
+    let file = info
+        .location()
+        .and_then(|location| Path::new(location.file()).file_name())
+        .map_or("<unknown>".into(), |file| file.to_string_lossy());
+    say(format!(
+        "Reporter: panic in {file}: {}",
+        message(info.payload())
+    ));
+}
+
+// The hook is global, so the previous one is put back afterwards.
+fn with_reporter(scenario: impl FnOnce()) {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(reporter));
+    scenario();
+    panic::set_hook(previous_hook);
+}
+
+// Each call owns a `LoudInt`, which gets dropped when the panic unwinds through the call.
+fn count_down(n: i32) {
+    let _guard = LoudInt(n);
+    if n == 0 {
+        panic!("reached zero");
+    }
+    count_down(n - 1);
+}
+
+// Unwinding drops everything on the way, from the most recent, like returning from functions.
+// `catch_unwind` stops the unwinding, what is outside of it is left intact.
+fn unwinding() {
+    let survivor = LoudInt(42);
+    let result = panic::catch_unwind(|| count_down(2));
+    if let Err(payload) = result {
+        say(format!("Caught: {}", message(&*payload)));
+    }
+    say(format!("[{}] I'm still here", survivor.0));
+}
+
+// A panic can be caught for a cleanup and then sent on its way with `resume_unwind`,
+// which doesn't call the hook again.
+fn resuming() {
+    let result = panic::catch_unwind(|| {
+        let _outer = LoudInt(1);
+        let inner = panic::catch_unwind(|| {
+            let _inner = LoudInt(2);
+            panic!("something went wrong");
+        });
+        if let Err(payload) = inner {
+            say("Cleaning up and passing the panic on".to_owned());
+            panic::resume_unwind(payload);
+        }
+    });
+    if let Err(payload) = result {
+        say(format!("Caught again: {}", message(&*payload)));
+    }
+}
+
+struct PanickyDrop;
+
+impl Drop for PanickyDrop {
+    fn drop(&mut self) {
+        panic!("panicking in drop");
+    }
+}
+
+// A panic during unwinding can't be unwound, so the whole process is aborted:
+// `catch_unwind` doesn't help, and nothing else gets dropped.
+fn double_panic() {
+    let _never_dropped = LoudInt(7);
+    let _ = panic::catch_unwind(|| {
+        let _panicky = PanickyDrop;
+        panic!("the first panic");
+    });
+    say("Unreachable".to_owned());
+}
+
+const SCENARIOS: [(&str, fn()); 3] = [
+    ("unwinding", unwinding),
+    ("resuming", resuming),
+    ("double-panic", double_panic),
+];
+
+// All but the double panic, which would end the program.
+fn show_all() {
+    with_reporter(|| {
+        for (name, scenario) in &SCENARIOS[..2] {
+            println!("=== {name}");
+            scenario();
+        }
+    });
+}
+
+fn main() {
+    let Some(name) = std::env::args().nth(1) else {
+        show_all();
+        return;
+    };
+    match SCENARIOS
+        .iter()
+        .find(|(scenario_name, _)| *scenario_name == name)
+    {
+        Some((_, scenario)) => with_reporter(scenario),
+        None => {
+            let names: Vec<&str> = SCENARIOS.iter().map(|(name, _)| *name).collect();
+            eprintln!("Usage: dont_panic [{}]", names.join(" | "));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use std::sync::Mutex;
+
+    // The tests run in parallel, but there's only one hook to swap.
+    static HOOK: Mutex<()> = Mutex::new(());
+
+    fn take_log() -> Vec<String> {
+        LOG.with(|log| log.take())
+    }
+
+    #[test]
+    fn drops_during_unwinding() {
+        let _hook = HOOK.lock().unwrap();
+        take_log();
+        with_reporter(unwinding);
+        assert_eq!(
+            take_log(),
+            [
+                "Reporter: panic in dont_panic.rs: reached zero",
+                "[0] Farewell, unwinding!",
+                "[1] Farewell, unwinding!",
+                "[2] Farewell, unwinding!",
+                "Caught: reached zero",
+                "[42] I'm still here",
+                "[42] Farewell!",
+            ]
+        );
+    }
+
+    #[test]
+    fn resumed_panic_is_reported_once() {
+        let _hook = HOOK.lock().unwrap();
+        take_log();
+        with_reporter(resuming);
+        assert_eq!(
+            take_log(),
+            [
+                "Reporter: panic in dont_panic.rs: something went wrong",
+                "[2] Farewell, unwinding!",
+                "Cleaning up and passing the panic on",
+                "[1] Farewell, unwinding!",
+                "Caught again: something went wrong",
+            ]
+        );
+    }
+
+    // Runs in a separate process (this test binary again), as it aborts.
+    #[test]
+    fn double_panic_aborts() {
+        if std::env::var_os("DONT_PANIC_DOUBLE").is_some() {
+            with_reporter(double_panic);
+            return;
+        }
+
+        // The path of this test, without the name of the crate.
+        let (_, module) = module_path!().split_once("::").unwrap();
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args([&format!("{module}::double_panic_aborts"), "--exact"])
+            .args(["--nocapture", "--test-threads=1"])
+            .env("DONT_PANIC_DOUBLE", "1")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(!output.status.success());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            assert_eq!(output.status.signal(), Some(libc::SIGABRT));
+        }
+        assert!(stdout.contains("Reporter: panic in dont_panic.rs: the first panic\n"));
+        assert!(stdout.contains("Reporter: panic in dont_panic.rs: panicking in drop\n"));
+        assert!(!stdout.contains("Farewell"));
+        assert!(!stdout.contains("Unreachable"));
+    }
+}
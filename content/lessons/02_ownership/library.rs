@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+// The library owns its books. Inside the building they are lent out as references,
+// and the borrow checker makes sure that nobody reads a book while someone else annotates it.
+// Taking a book home can't be checked by the compiler, as it spans many calls,
+// so the library keeps a register of those and checks it at runtime.
+
+pub struct Book {
+    pub title: String,
+    pub text: String,
+    notes: Vec<String>,
+}
+
+impl Book {
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LendingError {
+    NoSuchBook(usize),
+    CheckedOut { id: usize, by: String },
+    NotCheckedOut(usize),
+    WrongMember { id: usize, by: String },
+}
+
+impl fmt::Display for LendingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LendingError::NoSuchBook(id) => write!(f, "there is no book #{id}"),
+            LendingError::CheckedOut { id, by } => write!(f, "book #{id} is checked out by {by}"),
+            LendingError::NotCheckedOut(id) => write!(f, "book #{id} is not checked out"),
+            LendingError::WrongMember { id, by } => {
+                write!(f, "book #{id} was checked out by {by}, not you")
+            }
+        }
+    }
+}
+
+// A book lent for reading. It borrows the library, so it can't outlive it,
+// and the library can't change while the loan lasts.
+pub struct Loan<'lib> {
+    book: &'lib Book,
+}
+
+impl<'lib> Loan<'lib> {
+    pub fn book(&self) -> &'lib Book {
+        self.book
+    }
+
+    // Dropping the loan returns the book just as well, this only says it out loud.
+    pub fn give_back(self) {}
+}
+
+// A book lent for annotating. It borrows the library mutably, so it's the only loan there is.
+pub struct AnnotationLoan<'lib> {
+    book: &'lib mut Book,
+}
+
+impl AnnotationLoan<'_> {
+    pub fn book(&self) -> &Book {
+        self.book
+    }
+
+    pub fn annotate(&mut self, note: &str) {
+        self.book.notes.push(note.to_owned());
+    }
+}
+
+#[derive(Default)]
+pub struct Library {
+    books: BTreeMap<usize, Book>,
+    next_id: usize,
+    // Which member took which book home.
+    register: BTreeMap<usize, String>,
+}
+
+impl Library {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, title: &str, text: &str) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        let book = Book {
+            title: title.to_owned(),
+            text: text.to_owned(),
+            notes: Vec::new(),
+        };
+        self.books.insert(id, book);
+        id
+    }
+
+    // A book that was taken home can't be lent in the library.
+    fn check_on_shelf(&self, id: usize) -> Result<(), LendingError> {
+        if !self.books.contains_key(&id) {
+            return Err(LendingError::NoSuchBook(id));
+        }
+        match self.register.get(&id) {
+            Some(member) => Err(LendingError::CheckedOut {
+                id,
+                by: member.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn lend(&self, id: usize) -> Result<Loan<'_>, LendingError> {
+        self.check_on_shelf(id)?;
+        Ok(Loan {
+            book: &self.books[&id],
+        })
+    }
+
+    pub fn lend_for_annotation(&mut self, id: usize) -> Result<AnnotationLoan<'_>, LendingError> {
+        self.check_on_shelf(id)?;
+        Ok(AnnotationLoan {
+            book: self.books.get_mut(&id).unwrap(),
+        })
+    }
+
+    pub fn check_out(&mut self, id: usize, member: &str) -> Result<(), LendingError> {
+        self.check_on_shelf(id)?;
+        self.register.insert(id, member.to_owned());
+        Ok(())
+    }
+
+    pub fn check_in(&mut self, id: usize, member: &str) -> Result<(), LendingError> {
+        match self.register.get(&id) {
+            None => Err(LendingError::NotCheckedOut(id)),
+            Some(by) if by != member => Err(LendingError::WrongMember { id, by: by.clone() }),
+            Some(_) => {
+                self.register.remove(&id);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn checked_out(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.register
+            .iter()
+            .map(|(id, member)| (*id, member.as_str()))
+    }
+
+    // The library gives the book away, together with its ownership.
+    pub fn withdraw(&mut self, id: usize) -> Result<Book, LendingError> {
+        self.check_on_shelf(id)?;
+        Ok(self.books.remove(&id).unwrap())
+    }
+}
+
+// `withdraw` needs the library mutably, while the loan still borrows it,
+// so returning a book the library no longer owns doesn't compile (E0502):
+// fn withdraw_while_lent(library: &mut Library) {
+//     let loan = library.lend(0).unwrap();
+//     let book = library.withdraw(0).unwrap();
+//     loan.give_back();
+// }
+
+// Reading while someone annotates doesn't compile either (E0502):
+// fn read_while_annotated(library: &mut Library) {
+//     let mut annotation = library.lend_for_annotation(0).unwrap();
+//     let loan = library.lend(0).unwrap();
+//     annotation.annotate("The end is near.");
+//     println!("{}", loan.book().text);
+// }
+
+fn report<T>(what: &str, result: Result<T, LendingError>) {
+    match result {
+        Ok(_) => println!("{what}: ok"),
+        Err(error) => println!("{what}: {error}"),
+    }
+}
+
+fn main() {
+    let mut library = Library::new();
+    let house = library.add("Merry", "Merry lived in a big old house. The end.");
+    let hobbit = library.add(
+        "The Hobbit",
+        "In a hole in the ground there lived a hobbit.",
+    );
+
+    // Any number of readers at once.
+    let first = library.lend(house).unwrap();
+    let second = library.lend(house).unwrap();
+    println!("[Reading] {}", first.book().text);
+    println!("[Reading too] {}", second.book().text);
+    first.give_back();
+    second.give_back();
+
+    // Only one annotator, once the readers are gone.
+    let mut annotation = library.lend_for_annotation(house).unwrap();
+    annotation.annotate("Short, but moving.");
+    println!("[Notes] {:?}", annotation.book().notes());
+
+    // Taking books home is checked at runtime.
+    report(
+        "Frodo checks out The Hobbit",
+        library.check_out(hobbit, "Frodo"),
+    );
+    report("Sam reads The Hobbit", library.lend(hobbit));
+    report("Sam checks in The Hobbit", library.check_in(hobbit, "Sam"));
+    for (id, member) in library.checked_out() {
+        println!("Book #{id} is at {member}'s");
+    }
+    report(
+        "Frodo checks in The Hobbit",
+        library.check_in(hobbit, "Frodo"),
+    );
+
+    let book = library.withdraw(house).unwrap();
+    println!("Withdrawn: {} with notes {:?}", book.title, book.notes());
+    report("Sam reads Merry", library.lend(house));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> (Library, usize) {
+        let mut library = Library::new();
+        let id = library.add("Merry", "Merry lived in a big old house. The end.");
+        (library, id)
+    }
+
+    #[test]
+    fn many_readers_at_once() {
+        let (library, id) = library();
+        let loans: Vec<Loan> = (0..3).map(|_| library.lend(id).unwrap()).collect();
+        assert!(loans.iter().all(|loan| loan.book().title == "Merry"));
+    }
+
+    #[test]
+    fn annotations_stay_in_the_book() {
+        let (mut library, id) = library();
+        library.lend_for_annotation(id).unwrap().annotate("first");
+        library.lend_for_annotation(id).unwrap().annotate("second");
+        assert_eq!(
+            library.lend(id).unwrap().book().notes(),
+            ["first", "second"]
+        );
+    }
+
+    #[test]
+    fn checked_out_books_are_not_lent() {
+        let (mut library, id) = library();
+        library.check_out(id, "Frodo").unwrap();
+        let checked_out = LendingError::CheckedOut {
+            id,
+            by: "Frodo".to_owned(),
+        };
+        assert_eq!(library.lend(id).err(), Some(checked_out));
+        assert!(library.lend_for_annotation(id).is_err());
+        assert!(library.check_out(id, "Sam").is_err());
+        assert!(library.withdraw(id).is_err());
+        assert_eq!(library.checked_out().collect::<Vec<_>>(), [(id, "Frodo")]);
+    }
+
+    #[test]
+    fn only_the_member_checks_in() {
+        let (mut library, id) = library();
+        assert_eq!(
+            library.check_in(id, "Frodo"),
+            Err(LendingError::NotCheckedOut(id))
+        );
+        library.check_out(id, "Frodo").unwrap();
+        assert_eq!(
+            library.check_in(id, "Sam"),
+            Err(LendingError::WrongMember {
+                id,
+                by: "Frodo".to_owned()
+            })
+        );
+        library.check_in(id, "Frodo").unwrap();
+        assert!(library.lend(id).is_ok());
+    }
+
+    #[test]
+    fn withdrawn_books_are_gone() {
+        let (mut library, id) = library();
+        let book = library.withdraw(id).unwrap();
+        assert_eq!(book.title, "Merry");
+        assert_eq!(library.lend(id).err(), Some(LendingError::NoSuchBook(id)));
+        assert_eq!(
+            library.withdraw(id).err(),
+            Some(LendingError::NoSuchBook(id))
+        );
+    }
+}
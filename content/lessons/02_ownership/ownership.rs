@@ -0,0 +1,211 @@
+use std::panic::Location;
+use std::sync::Mutex;
+
+// A wrapper that makes clones visible: every clone is recorded, together with the place
+// in the code that made it. Moves and copies are not - they don't call `clone`.
+#[derive(Debug, PartialEq)]
+pub struct Tracked<T>(pub T);
+
+static CLONES: Mutex<Vec<&'static Location<'static>>> = Mutex::new(Vec::new());
+
+impl<T: Clone> Clone for Tracked<T> {
+    // `#[track_caller]` makes `Location::caller()` the place where `clone` was called,
+    // instead of this line.
+    #[track_caller]
+    fn clone(&self) -> Self {
+        CLONES.lock().unwrap().push(Location::caller());
+        Tracked(self.0.clone())
+    }
+}
+
+// A copy is just a copy of the bytes, no code of ours runs.
+impl<T: Copy> Copy for Tracked<T> {}
+
+pub fn clone_count() -> usize {
+    CLONES.lock().unwrap().len()
+}
+
+// Returns the places of the clones made so far, and starts counting anew.
+pub fn take_clones() -> Vec<&'static Location<'static>> {
+    std::mem::take(&mut *CLONES.lock().unwrap())
+}
+
+fn describe(location: &Location) -> String {
+    if location.file() == file!() {
+        format!("line {}", location.line())
+    } else {
+        "inside std".to_owned()
+    }
+}
+
+fn count_animals(num: u32, animal: Tracked<String>) {
+    println!("{} {} ...", num, animal.0);
+}
+
+// Passing by value moves the string into the function, nothing is cloned.
+fn passing_by_value() {
+    let s = Tracked(String::from("sheep"));
+    count_animals(1, s);
+}
+
+// Using the value again requires a clone, one for each call but the last.
+fn explicit_clone() {
+    let s = Tracked(String::from("sheep"));
+    count_animals(1, s.clone());
+    count_animals(2, s.clone());
+    count_animals(3, s);
+}
+
+// Copying an int doesn't call `clone` at all.
+fn copying() {
+    let x = Tracked(5);
+    let y = x;
+    assert_eq!(x, y);
+}
+
+#[derive(Clone)]
+struct Sheep {
+    name: Tracked<String>,
+    wool: Tracked<String>,
+    age: Tracked<u32>,
+}
+
+fn dolly() -> Sheep {
+    Sheep {
+        name: Tracked(String::from("Dolly")),
+        wool: Tracked(String::from("white")),
+        age: Tracked(6),
+    }
+}
+
+// `..dolly` moves the remaining fields out of `dolly`, nothing is cloned.
+fn struct_update() {
+    let dolly = dolly();
+    let polly = Sheep {
+        name: Tracked(String::from("Polly")),
+        ..dolly
+    };
+    assert_eq!(polly.name.0, "Polly");
+    assert_eq!(polly.wool.0, "white");
+}
+
+// `..dolly.clone()` clones the whole sheep, including the name that is thrown away right after.
+// The clones of the fields are made by the derived `clone`, so they are located at the derive.
+fn struct_update_with_clone() {
+    let dolly = dolly();
+    let polly = Sheep {
+        name: Tracked(String::from("Polly")),
+        ..dolly.clone()
+    };
+    assert_eq!(polly.name.0, "Polly");
+    assert_eq!((polly.wool, polly.age), (dolly.wool, dolly.age));
+}
+
+// `iter()` gives references, and `cloned()` clones only the elements that reach it.
+// `into_iter()` moves the elements out of the vector instead.
+fn collecting() {
+    let flock: Vec<Tracked<String>> = ["Dolly", "Polly", "Molly"]
+        .into_iter()
+        .map(|name| Tracked(String::from(name)))
+        .collect();
+
+    let cloned: Vec<Tracked<String>> = flock
+        .iter()
+        .filter(|sheep| sheep.0 != "Molly")
+        .cloned()
+        .collect();
+    let moved: Vec<Tracked<String>> = flock.into_iter().collect();
+    assert_eq!(cloned, moved[..2]);
+}
+
+type Scenario = fn();
+
+// Each scenario with the exact number of clones it makes.
+const SCENARIOS: [(&str, Scenario, usize); 6] = [
+    ("passing by value", passing_by_value, 0),
+    ("explicit clone", explicit_clone, 2),
+    ("copying", copying, 0),
+    ("struct update", struct_update, 0),
+    ("struct update with clone", struct_update_with_clone, 3),
+    ("collecting", collecting, 2),
+];
+
+fn main() {
+    for (name, scenario, expected) in SCENARIOS {
+        println!("=== {name}");
+        take_clones();
+        scenario();
+        let clones = take_clones();
+        let places: Vec<String> = clones.iter().map(|location| describe(location)).collect();
+        println!("{} clone(s): {places:?}", clones.len());
+        assert_eq!(clones.len(), expected, "{name}");
+    }
+    assert_eq!(clone_count(), 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::MutexGuard;
+
+    // The clones are counted for the whole program, so the tests must not run at the same time.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn serial() -> MutexGuard<'static, ()> {
+        let guard = SERIAL
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        take_clones();
+        guard
+    }
+
+    #[test]
+    fn scenarios_make_exact_numbers_of_clones() {
+        let _guard = serial();
+        for (name, scenario, expected) in SCENARIOS {
+            scenario();
+            assert_eq!(take_clones().len(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn clones_are_located_at_the_call() {
+        let _guard = serial();
+        let s = Tracked(String::from("sheep"));
+        let line = line!() + 1;
+        let copies = [s.clone(), s.clone()];
+        assert_eq!(copies[1], s);
+        let clones = take_clones();
+        assert_eq!(clones.len(), 2);
+        for location in clones {
+            assert_eq!((location.file(), location.line()), (file!(), line));
+        }
+    }
+
+    #[test]
+    fn moves_and_copies_are_not_clones() {
+        let _guard = serial();
+        let s = Tracked(String::from("sheep"));
+        let moved = s;
+        let n = Tracked(42);
+        let (a, b) = (n, n);
+        assert_eq!(moved.0, "sheep");
+        assert_eq!(a, b);
+        assert_eq!(clone_count(), 0);
+    }
+
+    #[test]
+    fn cloning_a_vector_clones_every_element() {
+        let _guard = serial();
+        let flock = vec![
+            Tracked(String::from("Dolly")),
+            Tracked(String::from("Polly")),
+        ];
+        let copy = flock.clone();
+        assert_eq!(copy, flock);
+        // `Vec` clones the elements in std, not here.
+        let clones = take_clones();
+        assert_eq!(clones.len(), 2);
+        assert!(clones.iter().all(|location| location.file() != file!()));
+    }
+}
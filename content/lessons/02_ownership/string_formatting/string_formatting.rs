@@ -0,0 +1,213 @@
+use std::fmt::{self, Alignment, Display, Formatter};
+
+// Prints the format string next to what it produces, brackets show where the padding ends.
+macro_rules! show {
+    ($format:literal $($args:tt)*) => {
+        println!("{:<18} => [{}]", $format, format!($format $($args)*))
+    };
+}
+
+// The fields are only ever read by `Debug`, which the compiler doesn't count as using them.
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Farm {
+    name: &'static str,
+    animals: Vec<(&'static str, u32)>,
+}
+
+// A point on a plane. Its `Display` respects the options given in the format string:
+// the precision applies to each coordinate, the width (with fill and alignment) to the whole,
+// and the alternate form `{:#}` names the coordinates.
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (x, y) = match f.precision() {
+            Some(precision) => (
+                format!("{:.precision$}", self.x),
+                format!("{:.precision$}", self.y),
+            ),
+            None => (self.x.to_string(), self.y.to_string()),
+        };
+        let point = if f.alternate() {
+            format!("(x: {x}, y: {y})")
+        } else {
+            format!("({x}, {y})")
+        };
+
+        // `f.pad(&point)` would handle the width, but it also treats the precision
+        // as the maximum length and would cut the point short.
+        let Some(width) = f.width() else {
+            return f.write_str(&point);
+        };
+        let padding = width.saturating_sub(point.chars().count());
+        let (before, after) = match f.align() {
+            Some(Alignment::Right) => (padding, 0),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(Alignment::Left) | None => (0, padding),
+        };
+        let fill = f.fill().to_string();
+        write!(f, "{}{point}{}", fill.repeat(before), fill.repeat(after))
+    }
+}
+
+fn main() {
+    println!("Width, fill and alignment:");
+    show!("{:8}", "ducks");
+    show!("{:<8}", "ducks");
+    show!("{:>8}", "ducks");
+    show!("{:^8}", "ducks");
+    show!("{:*^9}", "ducks");
+    show!("{:8}", 42);
+    show!("{:<8}", 42);
+    show!("{:08}", -42);
+
+    println!("Signs and alternate forms:");
+    show!("{:+}", 42);
+    show!("{:+}", -42);
+    show!("{:x}", 255);
+    show!("{:#x}", 255);
+    show!("{:#X}", 255);
+    show!("{:#b}", 5);
+    show!("{:#o}", 8);
+    show!("{:#010b}", 5);
+
+    println!("Floating point:");
+    show!("{:.2}", 12.34567);
+    show!("{:08.3}", 12.34567);
+    show!("{:+08.3}", -12.34567);
+    show!("{:e}", 1234.5);
+    show!("{:E}", 0.00012);
+
+    println!("Arguments:");
+    let animal = "ducks";
+    let count = 42;
+    let width = 10;
+    show!("{0} {1} {0}", "a", "b");
+    show!("{count} {animal}");
+    show!("{n} {animal}", n = count + 1);
+    show!("{animal:>width$}");
+    show!("{:>1$}", animal, width);
+    show!("{:.*}", 2, 1.23456);
+
+    println!("Debug:");
+    let farm = Farm {
+        name: "Old MacDonald's",
+        animals: vec![("ducks", 42), ("cows", 2)],
+    };
+    show!("{:?}", animal);
+    show!("{:?}", farm);
+    println!("{{:#?}} =>\n{farm:#?}");
+
+    println!("Custom Display:");
+    let point = Point { x: 1.5, y: -2.0 };
+    show!("{}", point);
+    show!("{:.2}", point);
+    show!("{:#}", point);
+    show!("{:>16}", point);
+    show!("{:-^#20.1}", point);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_fill_and_alignment() {
+        // Strings are aligned to the left by default, numbers to the right.
+        assert_eq!(format!("{:8}", "ducks"), "ducks   ");
+        assert_eq!(format!("{:>8}", "ducks"), "   ducks");
+        assert_eq!(format!("{:^8}", "ducks"), " ducks  ");
+        assert_eq!(format!("{:*^9}", "ducks"), "**ducks**");
+        assert_eq!(format!("{:8}", 42), "      42");
+        assert_eq!(format!("{:<8}", 42), "42      ");
+        // Zero-padding goes after the sign.
+        assert_eq!(format!("{:08}", -42), "-0000042");
+        // The width is the minimum, nothing gets cut.
+        assert_eq!(format!("{:2}", "ducks"), "ducks");
+        // Counted in chars, not bytes.
+        assert_eq!(format!("{:>6}", "Mućka"), " Mućka");
+    }
+
+    #[test]
+    fn signs_and_alternate_forms() {
+        assert_eq!(format!("{:+}", 42), "+42");
+        assert_eq!(format!("{:+}", -42), "-42");
+        assert_eq!(format!("{:x}", 255), "ff");
+        assert_eq!(format!("{:#x}", 255), "0xff");
+        assert_eq!(format!("{:#X}", 255), "0xFF");
+        assert_eq!(format!("{:#b}", 5), "0b101");
+        assert_eq!(format!("{:#o}", 8), "0o10");
+        // The prefix counts into the width.
+        assert_eq!(format!("{:#010b}", 5), "0b00000101");
+    }
+
+    #[test]
+    fn floating_point() {
+        assert_eq!(format!("{:.2}", 12.34567), "12.35");
+        assert_eq!(format!("{:08.3}", 12.34567), "0012.346");
+        assert_eq!(format!("{:+08.3}", -12.34567), "-012.346");
+        assert_eq!(format!("{:e}", 1234.5), "1.2345e3");
+        assert_eq!(format!("{:E}", 0.00012), "1.2E-4");
+        assert_eq!(format!("{:.1e}", 1234.5), "1.2e3");
+    }
+
+    #[test]
+    fn arguments() {
+        let animal = "ducks";
+        let count = 42;
+        let width = 10;
+        assert_eq!(format!("{0} {1} {0}", "a", "b"), "a b a");
+        assert_eq!(format!("{count} {animal}"), "42 ducks");
+        assert_eq!(format!("{n} {animal}", n = count + 1), "43 ducks");
+        assert_eq!(format!("{animal:>width$}"), "     ducks");
+        assert_eq!(format!("{:>1$}", animal, width), "     ducks");
+        assert_eq!(format!("{:.*}", 2, 1.23456), "1.23");
+        assert_eq!(format!("{{{count}}}"), "{42}");
+    }
+
+    #[test]
+    fn debug() {
+        let farm = Farm {
+            name: "Old MacDonald's",
+            animals: vec![("ducks", 42), ("cows", 2)],
+        };
+        assert_eq!(format!("{:?}", "ducks"), "\"ducks\"");
+        assert_eq!(
+            format!("{farm:?}"),
+            r#"Farm { name: "Old MacDonald's", animals: [("ducks", 42), ("cows", 2)] }"#
+        );
+        assert_eq!(
+            format!("{farm:#?}"),
+            r#"Farm {
+    name: "Old MacDonald's",
+    animals: [
+        (
+            "ducks",
+            42,
+        ),
+        (
+            "cows",
+            2,
+        ),
+    ],
+}"#
+        );
+    }
+
+    #[test]
+    fn point_honours_the_options() {
+        let point = Point { x: 1.5, y: -2.0 };
+        assert_eq!(format!("{point}"), "(1.5, -2)");
+        assert_eq!(format!("{point:.2}"), "(1.50, -2.00)");
+        assert_eq!(format!("{point:#}"), "(x: 1.5, y: -2)");
+        assert_eq!(format!("{point:12}"), "(1.5, -2)   ");
+        assert_eq!(format!("{point:>12}"), "   (1.5, -2)");
+        assert_eq!(format!("{point:-^#20.1}"), "-(x: 1.5, y: -2.0)--");
+        // Too narrow to pad.
+        assert_eq!(format!("{point:3}"), "(1.5, -2)");
+    }
+}
@@ -0,0 +1,247 @@
+use std::ops::Range;
+
+// Words are separated by whitespace and punctuation - in any language, not only ASCII.
+// Everything here only borrows the text: the words are slices of it, nothing is allocated.
+
+// `std` knows which chars are whitespace, but not which are punctuation beyond ASCII,
+// so here are the most common blocks of it.
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(c,
+            '¡' | '§' | '«' | '¶' | '·' | '»' | '¿' // Latin-1
+            | '\u{2010}'..='\u{2027}' | '\u{2030}'..='\u{205E}' // General Punctuation
+            | '\u{2E00}'..='\u{2E7F}' // Supplemental Punctuation
+            | '\u{3001}'..='\u{3003}' | '\u{3008}'..='\u{3011}' | '\u{3014}'..='\u{301F}' // CJK
+            | '！'..='／' | '：'..='＠' | '［'..='｀' | '｛'..='･' // Fullwidth forms
+        )
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
+}
+
+// Whether `c`, which starts at byte `i` of `text`, belongs to a word.
+// An apostrophe does only between two letters, as in "don't".
+fn in_word(text: &str, i: usize, c: char) -> bool {
+    if is_apostrophe(c) {
+        // `i` comes from `char_indices`, so it's a char boundary and slicing there can't panic.
+        let before = text[..i].chars().next_back();
+        let after = text[i + c.len_utf8()..].chars().next();
+        return before.is_some_and(char::is_alphanumeric)
+            && after.is_some_and(char::is_alphanumeric);
+    }
+    !c.is_whitespace() && !is_punctuation(c)
+}
+
+// Byte ranges of the words, so that they can be found in the text again.
+// Only `text[front..back]` is still to be searched.
+pub struct Spans<'a> {
+    text: &'a str,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for Spans<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let mut start = None;
+        for (i, c) in self.text[self.front..self.back].char_indices() {
+            let i = self.front + i;
+            match (start, in_word(self.text, i, c)) {
+                (None, true) => start = Some(i),
+                (Some(start), false) => {
+                    self.front = i;
+                    return Some(start..i);
+                }
+                _ => {}
+            }
+        }
+        self.front = self.back;
+        start.map(|start| start..self.back)
+    }
+}
+
+impl DoubleEndedIterator for Spans<'_> {
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        let mut end = None;
+        for (i, c) in self.text[self.front..self.back].char_indices().rev() {
+            let i = self.front + i;
+            match (end, in_word(self.text, i, c)) {
+                (None, true) => end = Some(i + c.len_utf8()),
+                (Some(end), false) => {
+                    let start = i + c.len_utf8();
+                    self.back = start;
+                    return Some(start..end);
+                }
+                _ => {}
+            }
+        }
+        self.back = self.front;
+        end.map(|end| self.front..end)
+    }
+}
+
+pub fn spans(text: &str) -> Spans<'_> {
+    Spans {
+        text,
+        front: 0,
+        back: text.len(),
+    }
+}
+
+// The words themselves. The lifetime says that they live as long as the text,
+// not as long as the iterator.
+pub struct Words<'a> {
+    spans: Spans<'a>,
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let span = self.spans.next()?;
+        Some(&self.spans.text[span])
+    }
+}
+
+impl DoubleEndedIterator for Words<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let span = self.spans.next_back()?;
+        Some(&self.spans.text[span])
+    }
+}
+
+pub fn words(text: &str) -> Words<'_> {
+    Words { spans: spans(text) }
+}
+
+// Counting from 0, like indices.
+pub fn nth_word(text: &str, n: usize) -> Option<&str> {
+    words(text).nth(n)
+}
+
+// Searches from the end, without going through all the words before.
+pub fn last_word(text: &str) -> Option<&str> {
+    words(text).next_back()
+}
+
+fn main() {
+    let text = "Zażółć gęślą jaźń — don't panic! «Ça va?» 你好，世界。";
+
+    for (word, span) in words(text).zip(spans(text)) {
+        println!("{word:?} at bytes {span:?}");
+    }
+    println!("The third word: {:?}", nth_word(text, 2));
+    println!("The last word: {:?}", last_word(text));
+    println!("No words: {:?}", last_word(" ... "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn all_words(text: &str) -> Vec<&str> {
+        words(text).collect()
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(all_words("hello world"), ["hello", "world"]);
+        assert_eq!(all_words("  hello,world!  "), ["hello", "world"]);
+        assert_eq!(all_words(""), Vec::<&str>::new());
+        assert_eq!(all_words(" \t\n.,;"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn unicode_whitespace_and_punctuation() {
+        // A no-break space, an em dash, an ideographic space and CJK punctuation.
+        let text = "Zażółć\u{a0}gęślą—jaźń\u{3000}你好，世界。¿Qué?";
+        assert_eq!(
+            all_words(text),
+            ["Zażółć", "gęślą", "jaźń", "你好", "世界", "Qué"]
+        );
+    }
+
+    #[test]
+    fn apostrophes() {
+        assert_eq!(
+            all_words("don't 'quote' rock ’n’ roll"),
+            ["don't", "quote", "rock", "n", "roll"]
+        );
+    }
+
+    #[test]
+    fn nth_and_last() {
+        let text = "one, two; three.";
+        assert_eq!(nth_word(text, 0), Some("one"));
+        assert_eq!(nth_word(text, 2), Some("three"));
+        assert_eq!(nth_word(text, 3), None);
+        assert_eq!(last_word(text), Some("three"));
+        assert_eq!(last_word("…"), None);
+    }
+
+    #[test]
+    fn spans_are_byte_offsets() {
+        let text = "żółw — łoś";
+        let spans: Vec<_> = spans(text).collect();
+        assert_eq!(spans, [0..7, 12..17]);
+        assert_eq!(&text[spans[1].clone()], "łoś");
+    }
+
+    #[test]
+    fn words_are_slices_of_the_text() {
+        let text = String::from("nothing is copied, not even once");
+        let range = text.as_bytes().as_ptr_range();
+        for word in words(&text) {
+            assert!(range.contains(&word.as_ptr()));
+        }
+    }
+
+    #[test]
+    fn both_ends_meet() {
+        let text = "a bb, ccc; dddd";
+        let mut words = words(text);
+        assert_eq!(words.next(), Some("a"));
+        assert_eq!(words.next_back(), Some("dddd"));
+        assert_eq!(words.next_back(), Some("ccc"));
+        assert_eq!(words.next(), Some("bb"));
+        assert_eq!(words.next(), None);
+        assert_eq!(words.next_back(), None);
+    }
+
+    // Every substring (at char boundaries) of texts full of multi-byte chars:
+    // nothing panics, spans are at char boundaries, and both directions agree.
+    #[test]
+    fn never_slices_inside_a_char() {
+        let pieces = [
+            "a", "ż", "€", "🦀", "e\u{301}", " ", "\u{a0}", "—", "’", "'", ",", "。", "你",
+        ];
+        let mut rng = StdRng::seed_from_u64(2137);
+        for _ in 0..200 {
+            let text: String = (0..12).map(|_| *pieces.choose(&mut rng).unwrap()).collect();
+            let boundaries: Vec<usize> = (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .collect();
+            for &start in &boundaries {
+                for &end in boundaries.iter().filter(|&&end| end >= start) {
+                    let text = &text[start..end];
+                    for span in spans(text) {
+                        assert!(
+                            text.is_char_boundary(span.start) && text.is_char_boundary(span.end)
+                        );
+                        assert!(!span.is_empty());
+                    }
+                    let forward = all_words(text);
+                    let mut backward: Vec<&str> = words(text).rev().collect();
+                    backward.reverse();
+                    assert_eq!(forward, backward, "{text:?}");
+                    assert_eq!(last_word(text), forward.last().copied());
+                }
+            }
+        }
+    }
+}
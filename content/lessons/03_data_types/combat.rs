@@ -0,0 +1,241 @@
+// A module in its own file, declared with `mod combat;` in `data_types.rs`.
+// Being a child module, it can see the private fields of `Hero`.
+use super::Hero;
+use rand::Rng;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Monster {
+    name: String,
+    level: u32,
+}
+
+impl Monster {
+    pub fn new(name: &str, level: u32) -> Monster {
+        Monster {
+            name: name.to_owned(),
+            level,
+        }
+    }
+
+    // The message goes to the battle log.
+    fn die(self) -> String {
+        format!("The {} is no more.", self.name)
+    }
+}
+
+// Whoever is left standing. The other one has died, and is gone for good.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Survivor {
+    Hero(Hero),
+    Monster(Monster),
+}
+
+// What both sides have in common during the fight.
+struct Fighter {
+    name: String,
+    level: u32,
+    health: u32,
+    attack: u32,
+    defence: u32,
+}
+
+impl Fighter {
+    fn new(name: &str, level: u32, attack: u32, defence: u32) -> Fighter {
+        Fighter {
+            name: name.to_owned(),
+            level,
+            health: 20 + 10 * level,
+            attack,
+            defence,
+        }
+    }
+
+    // A die roll, plus more for higher levels and better weapons, minus the target's armour.
+    // Every hit deals at least 1 damage, so every battle ends.
+    fn damage(&self, target: &Fighter, rng: &mut impl Rng) -> u32 {
+        let damage = rng.gen_range(1..=6) + 2 * self.level + self.attack;
+        damage.saturating_sub(target.defence).max(1)
+    }
+
+    fn initiative(&self, rng: &mut impl Rng) -> u32 {
+        rng.gen_range(1..=20) + self.level
+    }
+}
+
+// Experience for defeating a monster of the given level.
+fn reward(level: u32) -> u32 {
+    50 * level
+}
+
+// The hero and the monster are moved in: one of them comes back, the other one dies.
+pub fn battle(mut hero: Hero, monster: Monster, rng: &mut impl Rng) -> (Survivor, Vec<String>) {
+    let inventory = &hero.inventory;
+    let attack = inventory.weapon.as_ref().map_or(0, |weapon| weapon.damage);
+    let defence = inventory.armour.as_ref().map_or(0, |armour| armour.defence);
+    // Index 0 is the hero, 1 is the monster.
+    let mut fighters = [
+        Fighter::new(&hero.name, hero.level, attack, defence),
+        Fighter::new(&monster.name, monster.level, 0, 0),
+    ];
+    let mut log = vec![format!(
+        "{} (level {}, {} HP) meets {} (level {}, {} HP)",
+        fighters[0].name,
+        fighters[0].level,
+        fighters[0].health,
+        fighters[1].name,
+        fighters[1].level,
+        fighters[1].health
+    )];
+
+    let mut round = 1;
+    let loser = 'battle: loop {
+        let initiative = [fighters[0].initiative(rng), fighters[1].initiative(rng)];
+        // Ties go to the hero.
+        let order = if initiative[0] >= initiative[1] {
+            [0, 1]
+        } else {
+            [1, 0]
+        };
+        log.push(format!(
+            "Round {round}: initiative {} {}, {} {}",
+            fighters[0].name, initiative[0], fighters[1].name, initiative[1]
+        ));
+        for attacker in order {
+            let target = 1 - attacker;
+            let damage = fighters[attacker].damage(&fighters[target], rng);
+            fighters[target].health = fighters[target].health.saturating_sub(damage);
+            log.push(format!(
+                "  {} hits {} for {damage}, {} HP left",
+                fighters[attacker].name, fighters[target].name, fighters[target].health
+            ));
+            if fighters[target].health == 0 {
+                break 'battle target;
+            }
+        }
+        round += 1;
+    };
+
+    log.push(format!(
+        "{} falls after {round} round(s)",
+        fighters[loser].name
+    ));
+    if loser == 0 {
+        log.push(hero.die());
+        (Survivor::Monster(monster), log)
+    } else {
+        let experience = reward(monster.level);
+        let levels = hero.gain_experience(experience);
+        log.push(format!(
+            "{} gains {experience} experience and {levels} level(s)",
+            hero.name
+        ));
+        log.push(monster.die());
+        (Survivor::Hero(hero), log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::inventory::{Armour, Weapon};
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn fight(seed: u64, hero: Hero, monster: Monster) -> (Survivor, Vec<String>) {
+        battle(hero, monster, &mut StdRng::seed_from_u64(seed))
+    }
+
+    #[test]
+    fn same_seed_same_battle() {
+        for seed in 0..20 {
+            let hero = Hero::new(String::from("Ferris"));
+            let first = fight(seed, hero.clone(), Monster::new("Goblin", 1));
+            let second = fight(seed, hero, Monster::new("Goblin", 1));
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn battle_log_snapshot() {
+        let (survivor, log) = fight(
+            2137,
+            Hero::new(String::from("Ferris")),
+            Monster::new("Goblin", 1),
+        );
+        assert_eq!(
+            log,
+            [
+                "Ferris (level 1, 30 HP) meets Goblin (level 1, 30 HP)",
+                "Round 1: initiative Ferris 6, Goblin 14",
+                "  Goblin hits Ferris for 5, 25 HP left",
+                "  Ferris hits Goblin for 6, 24 HP left",
+                "Round 2: initiative Ferris 10, Goblin 17",
+                "  Goblin hits Ferris for 7, 18 HP left",
+                "  Ferris hits Goblin for 5, 19 HP left",
+                "Round 3: initiative Ferris 17, Goblin 18",
+                "  Goblin hits Ferris for 8, 10 HP left",
+                "  Ferris hits Goblin for 3, 16 HP left",
+                "Round 4: initiative Ferris 4, Goblin 9",
+                "  Goblin hits Ferris for 8, 2 HP left",
+                "  Ferris hits Goblin for 6, 10 HP left",
+                "Round 5: initiative Ferris 3, Goblin 8",
+                "  Goblin hits Ferris for 3, 0 HP left",
+                "Ferris falls after 5 round(s)",
+                "Here lies Ferris, a hero who reached level 1. RIP.",
+            ]
+        );
+        assert_eq!(survivor, Survivor::Monster(Monster::new("Goblin", 1)));
+    }
+
+    #[test]
+    fn higher_levels_hit_harder() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let weak = Fighter::new("Weak", 1, 0, 0);
+        let strong = Fighter::new("Strong", 10, 0, 0);
+        for _ in 0..100 {
+            let weak_damage = weak.damage(&strong, &mut rng);
+            let strong_damage = strong.damage(&weak, &mut rng);
+            assert!((3..=8).contains(&weak_damage));
+            assert!((21..=26).contains(&strong_damage));
+        }
+    }
+
+    #[test]
+    fn equipment_counts() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut hero = Hero::new(String::from("Ferris"));
+        hero.inventory.weapon = Some(Weapon {
+            name: String::from("Sword"),
+            damage: 100,
+        });
+        hero.inventory.armour = Some(Armour {
+            name: String::from("Shell"),
+            defence: 100,
+        });
+        // One hit is enough, and the monster can't hurt the hero much.
+        let (survivor, log) = battle(hero, Monster::new("Goblin", 1), &mut rng);
+        let Survivor::Hero(hero) = survivor else {
+            panic!("the hero lost: {log:?}");
+        };
+        assert_eq!(hero.level, 1);
+        assert_eq!(hero.experience, reward(1));
+        assert!(log.contains(&String::from("Goblin falls after 1 round(s)")));
+        assert!(log
+            .iter()
+            .filter(|line| line.contains("Goblin hits"))
+            .all(|line| line.contains("for 1,")));
+    }
+
+    #[test]
+    fn the_stronger_usually_wins() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let wins = (0..50)
+            .filter(|_| {
+                let hero = Hero::new(String::from("Ferris"));
+                let (survivor, _) = battle(hero, Monster::new("Dragon", 5), &mut rng);
+                matches!(survivor, Survivor::Hero(_))
+            })
+            .count();
+        assert!(wins < 5, "{wins}");
+    }
+}
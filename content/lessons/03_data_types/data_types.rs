@@ -1,4 +1,16 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+// The hero fights in `combat.rs`, which is a separate module.
+mod combat;
+// What the hero carries, where it can go and how it's saved are separate modules too.
+mod inventory;
+mod position;
+mod random;
+mod save;
+
+use inventory::{Armour, EquipError, Inventory, Item, Slot, Weapon};
+
+// `Hash` lets positions be keys of a `HashSet`, `Ord` - of a `BTreeSet`.
+// The derived order compares the fields one by one: first `x`, then `y`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 struct Position(i32, i32); // This is a "tuple struct".
 
 // Could Hero derive the Copy trait?
@@ -8,6 +20,7 @@ struct Hero {
     level: u32,
     experience: u32,
     position: Position,
+    inventory: Inventory,
 }
 
 // We can add methods to structs using the 'impl' keyword.
@@ -20,36 +33,59 @@ impl Hero {
             level: 1,
             experience: 0,
             position: Position(0, 0),
+            inventory: Inventory::default(),
         }
     }
+
+    // How much experience it takes to get from `level` to the next one.
+    // It grows with every level, so that levelling up gets harder.
+    // `None` past the highest level, where the amount wouldn't fit in a `u32`.
+    fn experience_to_next_level(level: u32) -> Option<u32> {
+        100u32.checked_mul(level)
+    }
 }
 
 // We can have multiple `impl` blocks for one struct.
 impl Hero {
     // Instance method. The first argument (self) is the calling instance,
     // just like `self` in Python and `this` in C++.
-    fn distance(&self, pos: Position) -> u32 {
+    fn distance(&self, pos: Position) -> u64 {
         // For convenience, we don't have to type the argument as `self: &Self`.
-        // The i-th field of a tuple or a tuple struct can be accessed through 'tuple.i'.
-        // Do not abuse this syntax, though; it's often cleaner to perform
-        // pattern matching to decompose the tuple.
-        (pos.0 - self.position.0).unsigned_abs() + (pos.1 - self.position.1).unsigned_abs()
+        self.position.manhattan(pos)
     }
 
     // Mutable borrow of self allows to change instance fields.
-    fn level_up(&mut self) {
+    // Returns how many levels were gained - a lot of experience at once can give many.
+    fn gain_experience(&mut self, amount: u32) -> u32 {
         // Again, we don't have to type the argument as `self: &mut Self`.
-        self.experience = 0;
-        self.level += 1;
+        self.experience = self.experience.saturating_add(amount);
+        let mut levels = 0;
+        while let Some(needed) = Hero::experience_to_next_level(self.level) {
+            if self.experience < needed {
+                break;
+            }
+            // Whatever is left over counts towards the next level.
+            self.experience -= needed;
+            self.level += 1;
+            levels += 1;
+        }
+        levels
+    }
+
+    // Gains just enough experience to reach the next level, if there is one.
+    fn level_up(&mut self) {
+        if let Some(needed) = Hero::experience_to_next_level(self.level) {
+            self.gain_experience(needed - self.experience);
+        }
     }
 
     // 'self' is not borrowed here and will be moved into the method.
-    fn die(self) {
-        println!(
+    fn die(self) -> String {
+        // The `self: Self` is dropped at the end, only the epitaph is left.
+        format!(
             "Here lies {}, a hero who reached level {}. RIP.",
             self.name, self.level
-        );
-        // The `self: Self` is now dropped.
+        )
     }
 }
 
@@ -58,11 +94,12 @@ fn main() {
     let mut hero: Hero = Hero::new(String::from("Ferris"));
     hero.level_up(); // 'self' is always passed implicitly as the first argument.
 
-    // Thanks to `..hero`, fields other than 'name' will be the same as in 'hero'.
+    // Thanks to `..hero`, fields other than 'name' and 'inventory' will be the same as in 'hero'.
     // In general, they are moved. Here, they are copied, because all missing fields
     // implement the `Copy` trait.
     let steve = Hero {
         name: String::from("Steve The Normal Guy"),
+        inventory: Inventory::default(),
         ..hero
     };
 
@@ -77,11 +114,43 @@ fn main() {
     hero.level_up();
     assert_eq!(hero, twin);
 
+    // Enough experience for two levels, with some left over.
+    let levels = hero.gain_experience(750);
+    println!(
+        "{} gained {} levels, now at level {} with {} experience",
+        hero.name, levels, hero.level, hero.experience
+    );
+
+    hero.inventory.add(Item::Weapon(Weapon {
+        name: String::from("Rusty sword"),
+        damage: 3,
+    }));
+    hero.inventory.add(Item::Potion {
+        name: String::from("Healing potion"),
+        healing: 20,
+    });
+    hero.inventory.add(Item::Armour(Armour {
+        name: String::from("Leather jacket"),
+        defence: 2,
+    }));
+    hero.inventory.equip(0).unwrap(); // The sword.
+    hero.inventory.equip(1).unwrap(); // The jacket, as the sword is no longer in the bag.
+    assert_eq!(hero.inventory.equip(0), Err(EquipError::NotEquippable));
+    println!("{} carries {:?}", hero.name, hero.inventory);
+
+    // Saving and loading gives back the same hero.
+    let saved = hero.save();
+    print!("{}", saved);
+    assert_eq!(Hero::load(&saved), Ok(hero.clone()));
+    hero.inventory.unequip(Slot::Weapon).unwrap();
+    hero.inventory.unequip(Slot::Armour).unwrap();
+
     // We can print out the struct's debug string
     // (which is implemented thanks to `Debug` trait) with '{:?}'.
     println!("print to stdout: {:?}", hero);
 
-    hero.die(); // 'hero' is not usable after this invocation, see the method's definiton.
+    // 'hero' is not usable after this invocation, see the method's definiton.
+    println!("{}", hero.die());
 
     // The `dbg!` macro prints debug strings to stderr along with file and line number.
     // `dbg!` takes its arguments by value, so it's better to borrow them to not have them
@@ -92,4 +161,88 @@ fn main() {
     let dist = steve.distance(pos); // No clone here as `Position` derives the `Copy` trait.
     println!("{:?}", pos);
     assert_eq!(dist, 42);
+
+    position::show();
+
+    // The twin goes to fight. Only one of them comes back.
+    let goblin = combat::Monster::new("Goblin", 2);
+    let (survivor, log) = combat::battle(twin, goblin, &mut random::rng());
+    for line in log {
+        println!("{}", line);
+    }
+    println!("Survivor: {:?}", survivor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hero_with_experience(amount: u32) -> Hero {
+        let mut hero = Hero::new(String::from("Ferris"));
+        hero.gain_experience(amount);
+        hero
+    }
+
+    #[test]
+    fn level_boundaries() {
+        // Level 1 -> 2 takes 100, 2 -> 3 takes 200, 3 -> 4 takes 300.
+        let cases = [
+            (0, 1, 0),
+            (99, 1, 99),
+            (100, 2, 0),
+            (299, 2, 199),
+            (300, 3, 0),
+            (599, 3, 299),
+            (600, 4, 0),
+        ];
+        for (amount, level, experience) in cases {
+            let hero = hero_with_experience(amount);
+            assert_eq!(
+                (hero.level, hero.experience),
+                (level, experience),
+                "{amount}"
+            );
+        }
+    }
+
+    #[test]
+    fn experience_carries_over_levels() {
+        let mut hero = hero_with_experience(50);
+        assert_eq!(hero.gain_experience(49), 0);
+        assert_eq!(hero.gain_experience(1), 1);
+        assert_eq!(hero.gain_experience(550), 2);
+        assert_eq!((hero.level, hero.experience), (4, 50));
+        // Gaining it in pieces is the same as all at once.
+        assert_eq!(hero, hero_with_experience(650));
+    }
+
+    #[test]
+    fn level_up_reaches_the_next_level() {
+        let mut hero = hero_with_experience(150);
+        hero.level_up();
+        assert_eq!((hero.level, hero.experience), (3, 0));
+    }
+
+    #[test]
+    fn huge_experience_does_not_overflow() {
+        let mut hero = hero_with_experience(u32::MAX);
+        hero.gain_experience(u32::MAX);
+        assert!(Hero::experience_to_next_level(hero.level)
+            .is_some_and(|needed| hero.experience < needed));
+    }
+
+    #[test]
+    fn the_highest_level_is_the_last() {
+        let mut hero = Hero::new(String::from("Ferris"));
+        hero.level = u32::MAX / 100;
+        hero.gain_experience(u32::MAX);
+        assert_eq!(hero.level, u32::MAX / 100 + 1);
+        assert_eq!(Hero::experience_to_next_level(hero.level), None);
+        hero.gain_experience(u32::MAX);
+        hero.level_up();
+        assert_eq!(
+            (hero.level, hero.experience),
+            (u32::MAX / 100 + 1, u32::MAX)
+        );
+    }
 }
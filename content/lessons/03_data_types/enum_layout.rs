@@ -0,0 +1,211 @@
+// How are enums represented in memory? Let's measure.
+//
+// Rust promises very little about the layout of its enums: only a few cases
+// (like `Option<&T>` being a nullable pointer) and enums with a `#[repr]` are specified.
+// Only those are checked with asserts. Everything else is marked as what the current
+// compiler does: it may change with the next version, or on another target.
+
+// `ShirtSize` comes from the example next door.
+#[path = "enums.rs"]
+mod enums;
+
+use enums::{NamedSize, ShirtSize};
+use std::mem::{align_of, size_of};
+use std::num::NonZeroU32;
+
+const POINTER: usize = size_of::<usize>();
+
+const CURRENT: &str = "(current compiler)";
+
+// Prints the size and alignment of a type.
+// With `same as`, also checks that they are guaranteed to be those of the other type.
+macro_rules! layout {
+    (@print $type:ty, $note:expr) => {
+        println!(
+            "{:<28} size {:>2}, align {}  {}",
+            stringify!($type),
+            size_of::<$type>(),
+            align_of::<$type>(),
+            $note
+        );
+    };
+    ($type:ty) => {
+        layout!(@print $type, CURRENT);
+    };
+    ($type:ty, same as $other:ty) => {
+        layout!(@print $type, concat!("(same as ", stringify!($other), ")"));
+        assert_eq!(
+            (size_of::<$type>(), align_of::<$type>()),
+            (size_of::<$other>(), align_of::<$other>()),
+            "layout of {}",
+            stringify!($type)
+        );
+    };
+}
+
+// The same data in three representations.
+// Without a `#[repr]` the compiler is free to pick the tag and to reorder fields.
+// They are only measured here, their fields are never read.
+#[allow(dead_code)]
+enum Reading {
+    Temperature(i8, u16),
+    Humidity(u8),
+}
+
+// `#[repr(u8)]`: the tag is a `u8`, placed first, followed by the fields of the variant.
+// The tag values can be chosen, just like in fieldless enums.
+#[allow(dead_code)]
+#[repr(u8)]
+enum ByteReading {
+    Temperature(i8, u16) = b'T',
+    Humidity(u8) = b'H',
+}
+
+// `#[repr(C)]`: laid out like the tagged union from `tagged_union.cpp` -
+// a C `enum` (usually 4 bytes) followed by a `union` of the variants.
+// The tags are numbered from 0, choosing them would need an integer type after all.
+#[allow(dead_code)]
+#[repr(C)]
+enum CReading {
+    Temperature(i8, u16),
+    Humidity(u8),
+}
+
+/// The bytes of a value, as they lie in memory.
+///
+/// # Safety
+///
+/// Every byte of the value must be initialized: no padding, and no payload shorter than
+/// the largest variant's. Reading uninitialized memory is undefined behaviour.
+unsafe fn bytes<T>(value: &T) -> &[u8] {
+    std::slice::from_raw_parts((value as *const T).cast::<u8>(), size_of::<T>())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("[{}]", bytes.join(" "))
+}
+
+// Dumps the bytes of a value. With `=>`, also checks that they are the guaranteed ones.
+// It's a macro, so that `$value` can be printed as written.
+macro_rules! dump {
+    (@bytes $type:ty: $value:expr, $note:expr) => {{
+        let value: $type = $value;
+        // SAFETY: all the values dumped below are free of padding and uninitialized bytes.
+        let bytes = unsafe { bytes(&value) }.to_vec();
+        let name = format!("{}: {}", stringify!($type), stringify!($value));
+        println!("{name:<40} {:<25} {}", hex(&bytes), $note);
+        bytes
+    }};
+    ($type:ty: $value:expr) => {
+        dump!(@bytes $type: $value, CURRENT);
+    };
+    ($type:ty: $value:expr => $expected:expr) => {
+        let bytes = dump!(@bytes $type: $value, "(guaranteed)");
+        assert_eq!(bytes, $expected, "bytes of {}", stringify!($value));
+    };
+}
+
+// Reads the tag of a `#[repr(u8)]` enum. It's guaranteed to be the first byte.
+fn byte_tag(reading: &ByteReading) -> u8 {
+    // SAFETY: a `#[repr(u8)]` enum starts with its `u8` tag, which is always initialized.
+    unsafe { *(reading as *const ByteReading).cast::<u8>() }
+}
+
+// Reads the tag of a `#[repr(C)]` enum: the C `enum` at its beginning.
+fn c_tag(reading: &CReading) -> i32 {
+    // SAFETY: a `#[repr(C)]` enum starts with its tag, a C `enum`, which is an `int` here.
+    unsafe { *(reading as *const CReading).cast::<i32>() }
+}
+
+fn main() {
+    println!("=== Sizes");
+    layout!(i32);
+    // A separate tag, padded to the alignment of `i32`.
+    layout!(Option<i32>);
+    // The tag of `Option<i32>` only uses the values 0 and 1,
+    // so `None` of the outer option can get another value of the same tag.
+    layout!(Option<Option<i32>>);
+    // References and boxes are never null, so null means `None` - no tag at all.
+    // This one is a promise, and so is the same for `NonZeroU32`.
+    layout!(Option<&u64>, same as &u64);
+    layout!(Option<Box<u64>>, same as Box<u64>);
+    layout!(Option<NonZeroU32>, same as u32);
+    layout!(Option<Option<&u64>>);
+    // A fieldless enum with 4 valid values out of the 256 of a byte.
+    layout!(NamedSize);
+    layout!(Option<NamedSize>);
+    // A tag, the `NamedSize` and the `u32` of `Numeric`, which needs 4-byte alignment.
+    layout!(ShirtSize);
+    layout!(Option<ShirtSize>);
+
+    println!("=== Representations");
+    layout!(Reading);
+    layout!(Option<Reading>);
+    layout!(ByteReading);
+    // Tag values nobody chose are still free for `Option`, even with a `#[repr]`.
+    layout!(Option<ByteReading>);
+    layout!(CReading);
+    layout!(Option<CReading>);
+
+    // Niches: invalid values of a type are used to store the tag of an enum around it.
+    // For these types every byte is meaningful, so we can look at all of them.
+    println!("=== Niches");
+    dump!(Option<NonZeroU32>: NonZeroU32::new(7) => 7u32.to_ne_bytes());
+    dump!(Option<NonZeroU32>: None => 0u32.to_ne_bytes());
+    dump!(Option<&u64>: None => [0; POINTER]);
+    dump!(Option<Box<u64>>: None => [0; POINTER]);
+    dump!(Option<char>: Some('a'));
+    // Currently one past the largest code point, `char::MAX`.
+    dump!(Option<char>: None);
+    dump!(Option<bool>: Some(true));
+    dump!(Option<bool>: None);
+    dump!(Option<Option<bool>>: Some(None));
+    dump!(Option<Option<bool>>: None);
+    dump!(Option<NamedSize>: Some(NamedSize::XL));
+    dump!(Option<NamedSize>: None);
+
+    // With a `#[repr]` the tags are where the reference says they are, with the values we chose.
+    println!("=== Tags");
+    for reading in [
+        ByteReading::Temperature(-5, 1013),
+        ByteReading::Humidity(40),
+    ] {
+        let tag = byte_tag(&reading);
+        println!("repr(u8) tag {tag:#04x} ({:?})", tag as char);
+    }
+    assert_eq!(byte_tag(&ByteReading::Humidity(40)), b'H');
+    for reading in [CReading::Temperature(-5, 1013), CReading::Humidity(40)] {
+        println!("repr(C) tag {}", c_tag(&reading));
+    }
+    assert_eq!(c_tag(&CReading::Humidity(40)), 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_where_the_repr_puts_them() {
+        assert_eq!(byte_tag(&ByteReading::Temperature(-5, 1013)), b'T');
+        assert_eq!(byte_tag(&ByteReading::Humidity(40)), b'H');
+        assert_eq!(c_tag(&CReading::Temperature(-5, 1013)), 0);
+        assert_eq!(c_tag(&CReading::Humidity(40)), 1);
+    }
+
+    #[test]
+    fn hex_dump() {
+        assert_eq!(hex(&[]), "[]");
+        assert_eq!(hex(&[0, 0x11, 0xff]), "[00 11 ff]");
+    }
+
+    #[test]
+    fn null_is_none() {
+        layout!(Option<&str>, same as &str);
+        layout!(Option<Box<[u8]>>, same as Box<[u8]>);
+        // Both sides need the same `0`: separate constants may or may not share an address.
+        static ZERO: u8 = 0;
+        dump!(Option<&u8>: Some(&ZERO) => (&ZERO as *const u8 as usize).to_ne_bytes());
+        dump!(Option<NonZeroU32>: NonZeroU32::new(u32::MAX) => [0xff; 4]);
+    }
+}
@@ -2,20 +2,193 @@
 #![allow(unused_variables)]
 #![allow(dead_code)]
 
-#[derive(Debug)]
-enum NamedSize {
+use std::cmp::Ordering;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// The derived order follows the order of the variants: `Small < Medium < Large < XL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NamedSize {
     Small,
     Medium,
     Large,
     XL,
 }
 
-#[derive(Debug)]
-enum ShirtSize {
+// A numeric size is an EU size, which is half of the chest circumference in centimetres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShirtSize {
     Named(NamedSize),
     Numeric(u32),
 }
 
+// Which numeric sizes each named size covers: EU sizes and US sizes (chest in inches).
+const SIZE_TABLE: [(NamedSize, RangeInclusive<u32>, RangeInclusive<u32>); 4] = [
+    (NamedSize::Small, 44..=46, 34..=36),
+    (NamedSize::Medium, 48..=50, 38..=40),
+    (NamedSize::Large, 52..=54, 42..=44),
+    (NamedSize::XL, 56..=58, 46..=48),
+];
+
+impl NamedSize {
+    const ALL: [NamedSize; 4] = [
+        NamedSize::Small,
+        NamedSize::Medium,
+        NamedSize::Large,
+        NamedSize::XL,
+    ];
+
+    fn eu_sizes(self) -> RangeInclusive<u32> {
+        // The table lists the sizes in the order of the variants.
+        SIZE_TABLE[self as usize].1.clone()
+    }
+
+    fn us_sizes(self) -> RangeInclusive<u32> {
+        SIZE_TABLE[self as usize].2.clone()
+    }
+
+    // Not every number has a name - there are sizes smaller than S and larger than XL.
+    fn from_eu(size: u32) -> Option<NamedSize> {
+        NamedSize::ALL
+            .into_iter()
+            .find(|named| named.eu_sizes().contains(&size))
+    }
+
+    fn from_us(size: u32) -> Option<NamedSize> {
+        NamedSize::ALL
+            .into_iter()
+            .find(|named| named.us_sizes().contains(&size))
+    }
+}
+
+impl fmt::Display for NamedSize {
+    // `{}` gives the label on the shirt, `{:#}` spells it out.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (short, long) = match self {
+            NamedSize::Small => ("S", "small"),
+            NamedSize::Medium => ("M", "medium"),
+            NamedSize::Large => ("L", "large"),
+            NamedSize::XL => ("XL", "extra large"),
+        };
+        f.write_str(if f.alternate() { long } else { short })
+    }
+}
+
+impl ShirtSize {
+    // A named size stands for the smallest EU size it covers.
+    fn eu_size(self) -> u32 {
+        match self {
+            ShirtSize::Named(named) => *named.eu_sizes().start(),
+            ShirtSize::Numeric(size) => size,
+        }
+    }
+
+    fn to_named(self) -> Option<NamedSize> {
+        match self {
+            ShirtSize::Named(named) => Some(named),
+            ShirtSize::Numeric(size) => NamedSize::from_eu(size),
+        }
+    }
+
+    // EU sizes go in steps of 2 and so do US sizes, 10 below them.
+    fn from_us(size: u32) -> Option<ShirtSize> {
+        let named = NamedSize::from_us(size)?;
+        let offset = size - named.us_sizes().start();
+        Some(ShirtSize::Numeric(named.eu_sizes().start() + offset))
+    }
+
+    fn us_size(self) -> Option<u32> {
+        let named = self.to_named()?;
+        let offset = self.eu_size() - named.eu_sizes().start();
+        Some(named.us_sizes().start() + offset)
+    }
+}
+
+// Sizes of both variants can be compared, by their EU sizes.
+// A named size comes right before the number it stands for (`M` < `48`),
+// so that different sizes are never equal.
+impl Ord for ShirtSize {
+    fn cmp(&self, other: &ShirtSize) -> Ordering {
+        let rank = |size: &ShirtSize| match size {
+            ShirtSize::Named(_) => 0,
+            ShirtSize::Numeric(_) => 1,
+        };
+        self.eu_size()
+            .cmp(&other.eu_size())
+            .then(rank(self).cmp(&rank(other)))
+    }
+}
+
+// `PartialOrd` has to agree with `Ord`, so it just uses it.
+impl PartialOrd for ShirtSize {
+    fn partial_cmp(&self, other: &ShirtSize) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for ShirtSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // Passing `f` on keeps the `{:#}` flag.
+            ShirtSize::Named(named) => named.fmt(f),
+            ShirtSize::Numeric(size) => write!(f, "EU {size}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseSizeError {
+    Empty,
+    UnknownName(String),
+    InvalidNumber(ParseIntError),
+}
+
+impl fmt::Display for ParseSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSizeError::Empty => write!(f, "no size given"),
+            ParseSizeError::UnknownName(name) => write!(f, "unknown size `{name}`"),
+            ParseSizeError::InvalidNumber(error) => write!(f, "invalid numeric size: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseSizeError {}
+
+// `FromStr` makes `"M".parse::<ShirtSize>()` work.
+// Names are case-insensitive, numbers may be preceded by "EU".
+impl FromStr for ShirtSize {
+    type Err = ParseSizeError;
+
+    fn from_str(input: &str) -> Result<ShirtSize, ParseSizeError> {
+        let input = input.trim();
+        let lowercase = input.to_lowercase();
+        let number = lowercase
+            .strip_prefix("eu")
+            .unwrap_or(&lowercase)
+            .trim_start();
+        if input.is_empty() {
+            return Err(ParseSizeError::Empty);
+        }
+        if number.starts_with(|c: char| c.is_ascii_digit()) {
+            return number
+                .parse()
+                .map(ShirtSize::Numeric)
+                .map_err(ParseSizeError::InvalidNumber);
+        }
+        let named = match lowercase.as_str() {
+            "s" | "small" => NamedSize::Small,
+            "m" | "medium" => NamedSize::Medium,
+            "l" | "large" => NamedSize::Large,
+            "xl" | "extra large" => NamedSize::XL,
+            _ => return Err(ParseSizeError::UnknownName(input.to_owned())),
+        };
+        Ok(ShirtSize::Named(named))
+    }
+}
+
 fn main() {
     println!(
         "Isn't it strange that some clothes' sizes are adjectives like {:?},",
@@ -25,4 +198,139 @@ fn main() {
         "but sometimes they are numbers like {:?}?",
         ShirtSize::Numeric(42)
     );
+
+    // With `FromStr` and `Display`, they can be read and written like people do.
+    let mut sizes: Vec<ShirtSize> = ["M", "xl", "42", "EU 50", "small"]
+        .iter()
+        .filter_map(|input| input.parse().ok())
+        .collect();
+    sizes.sort();
+    let sorted: Vec<String> = sizes.iter().map(|size| size.to_string()).collect();
+    println!("Sorted: {}", sorted.join(", "));
+
+    for size in sizes {
+        match size.to_named() {
+            Some(named) => println!("{size} is {named:#}, US {:?}", size.us_size()),
+            None => println!("{size} has no name"),
+        }
+    }
+    println!("US 40 is {:?}", ShirtSize::from_us(40));
+
+    match "XXL".parse::<ShirtSize>() {
+        Ok(size) => println!("Parsed {size}"),
+        Err(error) => println!("Can't parse: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<ShirtSize, ParseSizeError> {
+        input.parse()
+    }
+
+    #[test]
+    fn parses_names_and_numbers() {
+        assert_eq!(parse("M"), Ok(ShirtSize::Named(NamedSize::Medium)));
+        assert_eq!(parse("xl"), Ok(ShirtSize::Named(NamedSize::XL)));
+        assert_eq!(parse(" Large "), Ok(ShirtSize::Named(NamedSize::Large)));
+        assert_eq!(parse("42"), Ok(ShirtSize::Numeric(42)));
+        assert_eq!(parse("eu 48"), Ok(ShirtSize::Numeric(48)));
+        assert_eq!(parse("EU48"), Ok(ShirtSize::Numeric(48)));
+    }
+
+    #[test]
+    fn unknown_inputs_are_errors() {
+        assert_eq!(parse(""), Err(ParseSizeError::Empty));
+        assert_eq!(parse("   "), Err(ParseSizeError::Empty));
+        assert_eq!(
+            parse("XXL"),
+            Err(ParseSizeError::UnknownName(String::from("XXL")))
+        );
+        assert_eq!(
+            parse("EU"),
+            Err(ParseSizeError::UnknownName(String::from("EU")))
+        );
+        assert!(matches!(
+            parse("42.5"),
+            Err(ParseSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            parse("99999999999"),
+            Err(ParseSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(parse("-42"), Err(ParseSizeError::UnknownName(_))));
+        assert_eq!(parse("XXL").unwrap_err().to_string(), "unknown size `XXL`");
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let sizes = NamedSize::ALL
+            .into_iter()
+            .map(ShirtSize::Named)
+            .chain([ShirtSize::Numeric(42), ShirtSize::Numeric(0)]);
+        for size in sizes {
+            assert_eq!(parse(&size.to_string()), Ok(size));
+            assert_eq!(parse(&format!("{size:#}")), Ok(size));
+        }
+        assert_eq!(ShirtSize::Named(NamedSize::XL).to_string(), "XL");
+        assert_eq!(format!("{:#}", NamedSize::XL), "extra large");
+        assert_eq!(ShirtSize::Numeric(42).to_string(), "EU 42");
+    }
+
+    #[test]
+    fn conversion_tables() {
+        assert_eq!(NamedSize::from_eu(48), Some(NamedSize::Medium));
+        assert_eq!(NamedSize::from_eu(47), None);
+        assert_eq!(NamedSize::from_us(44), Some(NamedSize::Large));
+        assert_eq!(ShirtSize::Numeric(42).to_named(), None);
+        assert_eq!(ShirtSize::Numeric(60).to_named(), None);
+        assert_eq!(ShirtSize::from_us(40), Some(ShirtSize::Numeric(50)));
+        assert_eq!(ShirtSize::from_us(20), None);
+        assert_eq!(ShirtSize::Named(NamedSize::Small).us_size(), Some(34));
+
+        // Both ways, for every size in the table.
+        for named in NamedSize::ALL {
+            for eu in named.eu_sizes().step_by(2) {
+                let size = ShirtSize::Numeric(eu);
+                assert_eq!(size.to_named(), Some(named));
+                let us = size.us_size().unwrap();
+                assert!(named.us_sizes().contains(&us));
+                assert_eq!(ShirtSize::from_us(us), Some(size));
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_of_both_variants_sort_together() {
+        let mut sizes: Vec<ShirtSize> = ["58", "M", "xl", "42", "48", "S", "50"]
+            .into_iter()
+            .map(|input| parse(input).unwrap())
+            .collect();
+        sizes.sort();
+        let sorted: Vec<String> = sizes.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, ["EU 42", "S", "M", "EU 48", "EU 50", "XL", "EU 58"]);
+    }
+
+    #[test]
+    fn ordering_is_total() {
+        let sizes: Vec<ShirtSize> = NamedSize::ALL
+            .into_iter()
+            .map(ShirtSize::Named)
+            .chain((40..60).map(ShirtSize::Numeric))
+            .collect();
+        for a in &sizes {
+            for b in &sizes {
+                // Equal only when the same, and the opposite way round when swapped.
+                assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                for c in &sizes {
+                    if a < b && b < c {
+                        assert!(a < c);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,493 @@
+// Arithmetic expressions: parsed from text, evaluated, and simplified with pattern matching.
+// A module in its own file, declared with `mod expr;` in `pattern_matching.rs`.
+use std::collections::HashMap;
+use std::fmt;
+
+// An enum can contain itself, as long as there is a pointer in between.
+// Without the `Box` the size of `Expr` would be infinite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Lit(i64),
+    Var(String),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+}
+
+use Expr::{Add, Div, Lit, Mul, Neg, Sub, Var};
+
+// The values of the variables.
+pub type Env<'a> = HashMap<&'a str, i64>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EvalError {
+    UnknownVariable(String),
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnknownVariable(name) => write!(f, "unknown variable `{name}`"),
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::Overflow => write!(f, "overflow"),
+        }
+    }
+}
+
+impl Expr {
+    pub fn eval(&self, env: &Env) -> Result<i64, EvalError> {
+        let value = match self {
+            Lit(value) => Some(*value),
+            Var(name) => match env.get(name.as_str()) {
+                Some(value) => Some(*value),
+                None => return Err(EvalError::UnknownVariable(name.clone())),
+            },
+            Neg(a) => a.eval(env)?.checked_neg(),
+            Add(a, b) => a.eval(env)?.checked_add(b.eval(env)?),
+            Sub(a, b) => a.eval(env)?.checked_sub(b.eval(env)?),
+            Mul(a, b) => a.eval(env)?.checked_mul(b.eval(env)?),
+            Div(a, b) => match (a.eval(env)?, b.eval(env)?) {
+                (_, 0) => return Err(EvalError::DivisionByZero),
+                (a, b) => a.checked_div(b),
+            },
+        };
+        value.ok_or(EvalError::Overflow)
+    }
+
+    // Rewrites the expression into a simpler one with the same value, if it has one.
+    // The rules assume that evaluating succeeds: `x * 0` and `x - x` become `0` even if `x`
+    // is unknown, divides by zero or overflows, and the error goes away with `x`.
+    pub fn simplify(self) -> Expr {
+        // Children first, so that the rules below see them already simplified.
+        match self {
+            Lit(_) | Var(_) => self,
+            Neg(a) => neg(a.simplify()),
+            Add(a, b) => add(a.simplify(), b.simplify()),
+            Sub(a, b) => sub(a.simplify(), b.simplify()),
+            Mul(a, b) => mul(a.simplify(), b.simplify()),
+            Div(a, b) => div(a.simplify(), b.simplify()),
+        }
+    }
+
+    // Binds tighter the higher it is, used to put parentheses only where they are needed.
+    fn precedence(&self) -> u8 {
+        match self {
+            Add(..) | Sub(..) => 1,
+            Mul(..) | Div(..) => 2,
+            Neg(_) | Lit(i64::MIN..=-1) => 3,
+            Lit(_) | Var(_) => 4,
+        }
+    }
+}
+
+// The rules for each operation. The operands are taken out of their boxes first,
+// because patterns can't look inside a `Box` (box patterns are unstable).
+// When a rule produces a new operation, it goes through its rules as well.
+
+fn neg(a: Expr) -> Expr {
+    match a {
+        Lit(x) if x != i64::MIN => Lit(-x),
+        // --a = a
+        Neg(a) => *a,
+        a => Neg(Box::new(a)),
+    }
+}
+
+fn add(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Lit(x), Lit(y)) if x.checked_add(y).is_some() => Lit(x + y),
+        (Lit(0), a) | (a, Lit(0)) => a,
+        // a + -b = a - b
+        (a, Neg(b)) => sub(a, *b),
+        (a, b) if a == b => mul(Lit(2), a),
+        (a, b) => Add(Box::new(a), Box::new(b)),
+    }
+}
+
+fn sub(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Lit(x), Lit(y)) if x.checked_sub(y).is_some() => Lit(x - y),
+        (a, Lit(0)) => a,
+        (Lit(0), b) => neg(b),
+        // a - -b = a + b
+        (a, Neg(b)) => add(a, *b),
+        (a, b) if a == b => Lit(0),
+        (a, b) => Sub(Box::new(a), Box::new(b)),
+    }
+}
+
+fn mul(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        (Lit(x), Lit(y)) if x.checked_mul(y).is_some() => Lit(x * y),
+        (Lit(0), _) | (_, Lit(0)) => Lit(0),
+        (Lit(1), a) | (a, Lit(1)) => a,
+        (Lit(-1), a) | (a, Lit(-1)) => neg(a),
+        // -a * -b = a * b
+        (Neg(a), Neg(b)) => mul(*a, *b),
+        (a, b) => Mul(Box::new(a), Box::new(b)),
+    }
+}
+
+fn div(a: Expr, b: Expr) -> Expr {
+    match (a, b) {
+        // Only when the division is exact, so that `7 / 2 * 2` stays 6.
+        (Lit(x), Lit(y)) if x.checked_rem(y) == Some(0) => Lit(x / y),
+        (a, Lit(1)) => a,
+        (a, b) => Div(Box::new(a), Box::new(b)),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The operations are left-associative: `a - b - c` is `(a - b) - c`,
+        // so the right operand needs parentheses already at the same precedence.
+        let binary = |f: &mut fmt::Formatter<'_>, a: &Expr, op: &str, b: &Expr| {
+            let precedence = self.precedence();
+            if a.precedence() < precedence {
+                write!(f, "({a})")?;
+            } else {
+                write!(f, "{a}")?;
+            }
+            if b.precedence() <= precedence {
+                write!(f, " {op} ({b})")
+            } else {
+                write!(f, " {op} {b}")
+            }
+        };
+        match self {
+            // `-9223372036854775808` would be parsed as the negation of a number too large.
+            Lit(i64::MIN) => write!(f, "({} - 1)", i64::MIN + 1),
+            Lit(value) => write!(f, "{value}"),
+            Var(name) => write!(f, "{name}"),
+            Neg(a) if a.precedence() < 3 => write!(f, "-({a})"),
+            Neg(a) => write!(f, "-{a}"),
+            Add(a, b) => binary(f, a, "+", b),
+            Sub(a, b) => binary(f, a, "-", b),
+            Mul(a, b) => binary(f, a, "*", b),
+            Div(a, b) => binary(f, a, "/", b),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    MissingParen,
+    NumberTooLarge,
+}
+
+// Where the parser gave up, as a byte offset into the input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected `{c}`")?,
+            ParseErrorKind::MissingParen => write!(f, "expected `)`")?,
+            ParseErrorKind::NumberTooLarge => write!(f, "number too large")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+// A recursive-descent parser: one method for each level of precedence,
+// each calling the next one for its operands.
+//
+// expr  = term (("+" | "-") term)*
+// term  = unary (("*" | "/") unary)*
+// unary = "-" unary | atom
+// atom  = number | name | "(" expr ")"
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    // The next character that isn't whitespace.
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+        self.input[self.position..].chars().next()
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            position: self.position,
+            kind,
+        }
+    }
+
+    // Takes characters for as long as they pass the test.
+    fn take_while(&mut self, test: impl Fn(char) -> bool) -> &str {
+        let rest = &self.input[self.position..];
+        let end = rest.find(|c| !test(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.term()?;
+        loop {
+            let op: fn(Box<Expr>, Box<Expr>) -> Expr = match self.peek() {
+                Some('+') => Add,
+                Some('-') => Sub,
+                _ => return Ok(expr),
+            };
+            self.position += 1;
+            expr = op(Box::new(expr), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.unary()?;
+        loop {
+            let op: fn(Box<Expr>, Box<Expr>) -> Expr = match self.peek() {
+                Some('*') => Mul,
+                Some('/') => Div,
+                _ => return Ok(expr),
+            };
+            self.position += 1;
+            expr = op(Box::new(expr), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.peek() == Some('-') {
+            self.position += 1;
+            Ok(Neg(Box::new(self.unary()?)))
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+            Some('(') => {
+                self.position += 1;
+                let expr = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.position += 1;
+                        Ok(expr)
+                    }
+                    _ => Err(self.error(ParseErrorKind::MissingParen)),
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.position;
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                digits.parse().map(Lit).map_err(|_| ParseError {
+                    position: start,
+                    kind: ParseErrorKind::NumberTooLarge,
+                })
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Ok(Var(name.to_owned()))
+            }
+            Some(c) => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser { input, position: 0 };
+    let expr = parser.expr()?;
+    // Everything must be used up, `1 2` is not an expression.
+    match parser.peek() {
+        None => Ok(expr),
+        Some(c) => Err(parser.error(ParseErrorKind::UnexpectedChar(c))),
+    }
+}
+
+// Shows where exactly the parser stopped.
+fn report(input: &str, error: &ParseError) {
+    let column = input[..error.position].chars().count();
+    println!("  {input}");
+    println!("  {:>width$}^ {error}", "", width = column);
+}
+
+pub fn show() {
+    let env = Env::from([("x", 5), ("y", -3)]);
+    let inputs = [
+        "1 + 2 * 3",
+        "(x + 0) * 1 - -y",
+        "2 * (x + y) - 2 * (x + y) + z * 0",
+        "-(x - y) / (y + 3)",
+        "x * (y + ",
+        "x $ y",
+    ];
+    for input in inputs {
+        println!("{input}");
+        let expr = match parse(input) {
+            Ok(expr) => expr,
+            Err(error) => {
+                report(input, &error);
+                continue;
+            }
+        };
+        let simplified = expr.clone().simplify();
+        println!("  simplified: {simplified}");
+        match simplified.eval(&env) {
+            Ok(value) => println!("  value: {value}"),
+            Err(error) => println!("  error: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> Env<'static> {
+        Env::from([("x", 5), ("y", -3), ("zero", 0)])
+    }
+
+    #[test]
+    fn simplifies_and_evaluates() {
+        // Input, simplified form, value.
+        let cases = [
+            ("1 + 2 * 3", "7", Ok(7)),
+            ("(1 + 2) * 3", "9", Ok(9)),
+            ("x * 1", "x", Ok(5)),
+            ("1 * x", "x", Ok(5)),
+            ("x + 0", "x", Ok(5)),
+            ("0 + x * y", "x * y", Ok(-15)),
+            ("x - x", "0", Ok(0)),
+            ("x + x", "2 * x", Ok(10)),
+            ("x * 0 + y", "y", Ok(-3)),
+            ("--x", "x", Ok(5)),
+            ("-(x - y)", "-(x - y)", Ok(-8)),
+            ("x - -y", "x + y", Ok(2)),
+            ("x + -y", "x - y", Ok(8)),
+            ("-x * -y", "x * y", Ok(-15)),
+            ("x * -1", "-x", Ok(-5)),
+            ("0 - x", "-x", Ok(-5)),
+            ("-3 * x", "-3 * x", Ok(-15)),
+            ("12 / 4 / x", "3 / x", Ok(0)),
+            ("x / 1", "x", Ok(5)),
+            ("7 / 2", "7 / 2", Ok(3)),
+            ("x - (y - 1)", "x - (y - 1)", Ok(9)),
+            ("(x - y) - 1", "x - y - 1", Ok(7)),
+            ("x / (y * 2)", "x / (y * 2)", Ok(0)),
+            ("2 * (x + 3) - 2 * (x + 3)", "0", Ok(0)),
+            ("1 / 0", "1 / 0", Err(EvalError::DivisionByZero)),
+            ("x / zero", "x / zero", Err(EvalError::DivisionByZero)),
+            (
+                "a + 1",
+                "a + 1",
+                Err(EvalError::UnknownVariable(String::from("a"))),
+            ),
+            (
+                "9223372036854775807 + 1",
+                "9223372036854775807 + 1",
+                Err(EvalError::Overflow),
+            ),
+            (
+                "-9223372036854775807 - 1",
+                "(-9223372036854775807 - 1)",
+                Ok(i64::MIN),
+            ),
+            (
+                "x * (-9223372036854775807 - 1)",
+                "x * (-9223372036854775807 - 1)",
+                Err(EvalError::Overflow),
+            ),
+        ];
+        let env = env();
+        for (input, simplified, value) in cases {
+            let expr = parse(input).unwrap();
+            let result = expr.clone().simplify();
+            assert_eq!(result.to_string(), simplified, "{input}");
+            assert_eq!(result.eval(&env), value, "{input}");
+            // These keep their value when simplified, errors included.
+            assert_eq!(expr.eval(&env), value, "{input}");
+            // And the printed form reads back as the same expression.
+            assert_eq!(parse(simplified).unwrap().simplify(), result, "{input}");
+        }
+    }
+
+    #[test]
+    fn simplifying_can_remove_errors() {
+        // Original, and its error.
+        let cases = [
+            ("a * 0", EvalError::UnknownVariable(String::from("a"))),
+            ("1 / 0 * 0", EvalError::DivisionByZero),
+            ("(1 / 0) - (1 / 0)", EvalError::DivisionByZero),
+            (
+                "9223372036854775807 * 2 - 9223372036854775807 * 2",
+                EvalError::Overflow,
+            ),
+        ];
+        let env = env();
+        for (input, error) in cases {
+            let expr = parse(input).unwrap();
+            assert_eq!(expr.eval(&env), Err(error), "{input}");
+            assert_eq!(expr.simplify(), Lit(0), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let cases = [
+            ("", 0, ParseErrorKind::UnexpectedEnd),
+            ("1 +", 3, ParseErrorKind::UnexpectedEnd),
+            ("(1 + 2", 6, ParseErrorKind::MissingParen),
+            ("(1 + 2 3)", 7, ParseErrorKind::MissingParen),
+            ("1 2", 2, ParseErrorKind::UnexpectedChar('2')),
+            ("x $ y", 2, ParseErrorKind::UnexpectedChar('$')),
+            ("1 + )", 4, ParseErrorKind::UnexpectedChar(')')),
+            (
+                "1 + 99999999999999999999",
+                4,
+                ParseErrorKind::NumberTooLarge,
+            ),
+            ("zażółć + ?", 13, ParseErrorKind::UnexpectedChar('?')),
+        ];
+        for (input, position, kind) in cases {
+            assert_eq!(parse(input), Err(ParseError { position, kind }), "{input}");
+        }
+        assert_eq!(
+            parse("1 +").unwrap_err().to_string(),
+            "unexpected end of input at position 3"
+        );
+    }
+
+    #[test]
+    fn parses_with_precedence_and_associativity() {
+        let lit = |value| Box::new(Lit(value));
+        assert_eq!(
+            parse("1 - 2 - 3"),
+            Ok(Sub(Box::new(Sub(lit(1), lit(2))), lit(3)))
+        );
+        assert_eq!(
+            parse("1 + 2 * 3"),
+            Ok(Add(lit(1), Box::new(Mul(lit(2), lit(3)))))
+        );
+        assert_eq!(
+            parse(" -x_1*2 "),
+            Ok(Mul(
+                Box::new(Neg(Box::new(Var(String::from("x_1"))))),
+                lit(2)
+            ))
+        );
+    }
+
+    #[test]
+    fn overflowing_constants_are_not_folded() {
+        let expr = parse("9223372036854775807 * 2 - 1").unwrap().simplify();
+        assert_eq!(expr.to_string(), "9223372036854775807 * 2 - 1");
+        assert_eq!(expr.eval(&env()), Err(EvalError::Overflow));
+    }
+}
@@ -0,0 +1,128 @@
+// What the hero carries, in a module declared with `mod inventory;` in `data_types.rs`.
+// The fields are `pub`, so that the hero's modules can look inside.
+
+// Enum variants can hold different data, here each of them holds a different struct.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Item {
+    Weapon(Weapon),
+    Armour(Armour),
+    Potion { name: String, healing: u32 },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Weapon {
+    pub name: String,
+    pub damage: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Armour {
+    pub name: String,
+    pub defence: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Slot {
+    Weapon,
+    Armour,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum EquipError {
+    NoSuchItem,
+    NotEquippable,
+    NothingEquipped,
+}
+
+// `Default` gives an empty bag and empty slots, as `Vec` and `Option` have their defaults too.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Inventory {
+    pub bag: Vec<Item>,
+    // The slots can only hold what fits them - the types say so.
+    pub weapon: Option<Weapon>,
+    pub armour: Option<Armour>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, item: Item) {
+        self.bag.push(item);
+    }
+
+    // Moves the item from the bag to its slot, and whatever was in the slot back to the bag.
+    pub fn equip(&mut self, index: usize) -> Result<(), EquipError> {
+        if index >= self.bag.len() {
+            return Err(EquipError::NoSuchItem);
+        }
+        // `remove` gives us the item itself (not a reference), so we can move it into the slot.
+        let previous = match self.bag.remove(index) {
+            Item::Weapon(weapon) => self.weapon.replace(weapon).map(Item::Weapon),
+            Item::Armour(armour) => self.armour.replace(armour).map(Item::Armour),
+            potion @ Item::Potion { .. } => {
+                self.bag.insert(index, potion);
+                return Err(EquipError::NotEquippable);
+            }
+        };
+        if let Some(previous) = previous {
+            self.bag.push(previous);
+        }
+        Ok(())
+    }
+
+    pub fn unequip(&mut self, slot: Slot) -> Result<(), EquipError> {
+        // `take` moves the value out of the `Option`, leaving `None` in its place.
+        let item = match slot {
+            Slot::Weapon => self.weapon.take().map(Item::Weapon),
+            Slot::Armour => self.armour.take().map(Item::Armour),
+        };
+        let item = item.ok_or(EquipError::NothingEquipped)?;
+        self.bag.push(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sword(damage: u32) -> Item {
+        Item::Weapon(Weapon {
+            name: String::from("Sword"),
+            damage,
+        })
+    }
+
+    #[test]
+    fn equipping_swaps_items() {
+        let mut inventory = Inventory::default();
+        inventory.add(sword(1));
+        inventory.add(sword(2));
+        inventory.equip(0).unwrap();
+        inventory.equip(0).unwrap();
+        assert_eq!(inventory.bag, [sword(1)]);
+        assert_eq!(
+            inventory.weapon.as_ref().map(|weapon| weapon.damage),
+            Some(2)
+        );
+
+        inventory.unequip(Slot::Weapon).unwrap();
+        assert_eq!(inventory.bag, [sword(1), sword(2)]);
+        assert_eq!(
+            inventory.unequip(Slot::Weapon),
+            Err(EquipError::NothingEquipped)
+        );
+        assert_eq!(inventory.equip(2), Err(EquipError::NoSuchItem));
+    }
+
+    #[test]
+    fn potions_stay_in_the_bag() {
+        let mut inventory = Inventory::default();
+        let potion = Item::Potion {
+            name: String::from("Potion"),
+            healing: 5,
+        };
+        inventory.add(sword(1));
+        inventory.add(potion.clone());
+        assert_eq!(inventory.equip(1), Err(EquipError::NotEquippable));
+        assert_eq!(inventory.bag, [sword(1), potion]);
+    }
+}
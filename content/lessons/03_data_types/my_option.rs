@@ -0,0 +1,388 @@
+// There is nothing magical about `Option` - it's an ordinary enum from the standard library.
+// Here we write our own, with the methods we've seen and a few more.
+// Each of them is just a `match`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MyOption<T> {
+    MySome(T),
+    MyNone,
+}
+
+use MyOption::{MyNone, MySome};
+
+impl<T> MyOption<T> {
+    pub fn is_some(&self) -> bool {
+        matches!(self, MySome(_))
+    }
+
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            MySome(value) => value,
+            MyNone => default,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> MyOption<U> {
+        match self {
+            MySome(value) => MySome(f(value)),
+            MyNone => MyNone,
+        }
+    }
+
+    pub fn and_then<U>(self, f: impl FnOnce(T) -> MyOption<U>) -> MyOption<U> {
+        match self {
+            MySome(value) => f(value),
+            MyNone => MyNone,
+        }
+    }
+
+    // Keeps the value only if it passes the test.
+    pub fn filter(self, predicate: impl FnOnce(&T) -> bool) -> MyOption<T> {
+        match self {
+            MySome(value) if predicate(&value) => MySome(value),
+            _ => MyNone,
+        }
+    }
+
+    // A missing value becomes an error.
+    pub fn ok_or<E>(self, error: E) -> Result<T, E> {
+        match self {
+            MySome(value) => Ok(value),
+            MyNone => Err(error),
+        }
+    }
+
+    // Both values, or nothing.
+    pub fn zip<U>(self, other: MyOption<U>) -> MyOption<(T, U)> {
+        match (self, other) {
+            (MySome(a), MySome(b)) => MySome((a, b)),
+            _ => MyNone,
+        }
+    }
+
+    // Exactly one of the values.
+    pub fn xor(self, other: MyOption<T>) -> MyOption<T> {
+        match (self, other) {
+            (MySome(value), MyNone) | (MyNone, MySome(value)) => MySome(value),
+            _ => MyNone,
+        }
+    }
+
+    // `&MyOption<T>` to `MyOption<&T>`: looking inside without moving the value out.
+    pub fn as_ref(&self) -> MyOption<&T> {
+        match self {
+            MySome(value) => MySome(value),
+            MyNone => MyNone,
+        }
+    }
+
+    pub fn as_mut(&mut self) -> MyOption<&mut T> {
+        match self {
+            MySome(value) => MySome(value),
+            MyNone => MyNone,
+        }
+    }
+
+    // Leaves `MyNone` in place of the value, so it works through a `&mut`.
+    pub fn take(&mut self) -> MyOption<T> {
+        std::mem::replace(self, MyNone)
+    }
+
+    pub fn replace(&mut self, value: T) -> MyOption<T> {
+        std::mem::replace(self, MySome(value))
+    }
+
+    // `f` is only called when there is no value yet.
+    pub fn get_or_insert_with(&mut self, f: impl FnOnce() -> T) -> &mut T {
+        if self.is_none() {
+            *self = MySome(f());
+        }
+        match self {
+            MySome(value) => value,
+            MyNone => unreachable!("the value was just inserted"),
+        }
+    }
+
+    // An option is a collection of at most one element.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.as_ref(),
+        }
+    }
+}
+
+impl<T> MyOption<MyOption<T>> {
+    pub fn flatten(self) -> MyOption<T> {
+        match self {
+            MySome(inner) => inner,
+            MyNone => MyNone,
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: MyOption<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.take().into()
+    }
+}
+
+pub struct IntoIter<T> {
+    next: MyOption<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.next.take().into()
+    }
+}
+
+// `IntoIterator` is what makes `for value in option` work.
+impl<T> IntoIterator for MyOption<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { next: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MyOption<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+// Conversions both ways, so that our option can be used where the standard one is expected.
+impl<T> From<Option<T>> for MyOption<T> {
+    fn from(option: Option<T>) -> MyOption<T> {
+        match option {
+            Some(value) => MySome(value),
+            None => MyNone,
+        }
+    }
+}
+
+impl<T> From<MyOption<T>> for Option<T> {
+    fn from(option: MyOption<T>) -> Option<T> {
+        match option {
+            MySome(value) => Some(value),
+            MyNone => None,
+        }
+    }
+}
+
+fn checked_div(dividend: i32, divisor: i32) -> MyOption<i32> {
+    dividend.checked_div(divisor).into()
+}
+
+fn main() {
+    let number = MySome(42);
+    println!("map: {:?}", number.map(|x| x * 2));
+    println!("flatten: {:?}", MySome(MySome(42)).flatten());
+    println!(
+        "and_then: {:?}",
+        number
+            .and_then(|x| checked_div(x, 0))
+            .and_then(|x| checked_div(x, 2))
+    );
+    println!("filter: {:?}", number.filter(|x| x % 2 == 1));
+    println!("ok_or: {:?}", MyNone::<i32>.ok_or("no number"));
+    println!("zip: {:?}", number.zip(MySome("apples")));
+    println!("xor: {:?}", number.xor(MySome(7)));
+
+    let mut cache = MyNone;
+    let value = cache.get_or_insert_with(|| {
+        println!("computing...");
+        6 * 7
+    });
+    *value += 1;
+    // The second time there is nothing to compute.
+    cache.get_or_insert_with(|| unreachable!());
+    println!("cache: {cache:?}");
+
+    if let MySome(value) = cache.as_mut() {
+        *value -= 1;
+    }
+    let old = cache.replace(0);
+    println!("replaced {old:?} with {cache:?}, took {:?}", cache.take());
+
+    // Iterating over options skips the missing values.
+    let options = [MySome(1), MyNone, MySome(3)];
+    let total: i32 = options.iter().flat_map(MyOption::iter).sum();
+    println!("total: {total}");
+    for value in MySome("only once") {
+        println!("{value}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random(rng: &mut StdRng) -> Option<i32> {
+        // Small numbers, so that zeros and equal values come up often.
+        rng.gen_bool(0.5).then(|| rng.gen_range(-10..=10))
+    }
+
+    // Runs `test` on many random pairs of standard options.
+    // The tests turn them into ours with `my` and compare the results of both.
+    fn check(test: impl Fn(Option<i32>, Option<i32>)) {
+        let mut rng = StdRng::seed_from_u64(2137);
+        for _ in 0..1000 {
+            test(random(&mut rng), random(&mut rng));
+        }
+    }
+
+    fn my(option: Option<i32>) -> MyOption<i32> {
+        option.into()
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        check(|a, _| assert_eq!(Option::from(my(a)), a));
+    }
+
+    #[test]
+    fn predicates_and_defaults() {
+        check(|a, _| {
+            assert_eq!(my(a).is_some(), a.is_some());
+            assert_eq!(my(a).is_none(), a.is_none());
+            assert_eq!(my(a).unwrap_or(-1), a.unwrap_or(-1));
+        });
+    }
+
+    #[test]
+    fn map() {
+        check(|a, _| assert_eq!(Option::from(my(a).map(|x| x * 3)), a.map(|x| x * 3)));
+        check(|a, _| {
+            assert_eq!(
+                Option::from(my(a).map(|x| x.to_string())),
+                a.map(|x| x.to_string())
+            )
+        });
+    }
+
+    #[test]
+    fn and_then() {
+        check(|a, b| {
+            let divisor = b.unwrap_or(0);
+            let ours = my(a).and_then(|x| checked_div(x, divisor));
+            assert_eq!(Option::from(ours), a.and_then(|x| x.checked_div(divisor)));
+        });
+    }
+
+    #[test]
+    fn flatten() {
+        check(|a, b| {
+            // `b` decides whether there's an outer value at all.
+            let nested = b.map(|_| a);
+            let ours = b.map(|_| my(a));
+            assert_eq!(Option::from(my_nested(ours).flatten()), nested.flatten());
+        });
+    }
+
+    fn my_nested(option: Option<MyOption<i32>>) -> MyOption<MyOption<i32>> {
+        option.into()
+    }
+
+    #[test]
+    fn filter() {
+        check(|a, _| {
+            let even = |x: &i32| x % 2 == 0;
+            assert_eq!(Option::from(my(a).filter(even)), a.filter(even));
+        });
+    }
+
+    #[test]
+    fn ok_or() {
+        check(|a, _| assert_eq!(my(a).ok_or("missing"), a.ok_or("missing")));
+    }
+
+    #[test]
+    fn zip() {
+        check(|a, b| assert_eq!(Option::from(my(a).zip(my(b))), a.zip(b)));
+    }
+
+    #[test]
+    fn xor() {
+        check(|a, b| assert_eq!(Option::from(my(a).xor(my(b))), a.xor(b)));
+    }
+
+    #[test]
+    fn as_ref_and_as_mut() {
+        check(|a, _| {
+            let ours = my(a);
+            assert_eq!(Option::from(ours.as_ref().map(|x| *x)), a.as_ref().copied());
+
+            let (mut ours, mut theirs) = (ours, a);
+            if let MySome(x) = ours.as_mut() {
+                *x += 100;
+            }
+            if let Some(x) = theirs.as_mut() {
+                *x += 100;
+            }
+            assert_eq!(Option::from(ours), theirs);
+        });
+    }
+
+    #[test]
+    fn take_and_replace() {
+        check(|a, b| {
+            let (mut ours, mut theirs) = (my(a), a);
+            assert_eq!(Option::from(ours.take()), theirs.take());
+            assert_eq!(Option::from(ours), theirs);
+
+            let (mut ours, mut theirs) = (my(a), a);
+            let value = b.unwrap_or(0);
+            assert_eq!(Option::from(ours.replace(value)), theirs.replace(value));
+            assert_eq!(Option::from(ours), theirs);
+        });
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        check(|a, b| {
+            let value = b.unwrap_or(0);
+            let (mut ours, mut theirs) = (my(a), a);
+            let mut our_calls = 0;
+            let mut their_calls = 0;
+            *ours.get_or_insert_with(|| {
+                our_calls += 1;
+                value
+            }) += 1;
+            *theirs.get_or_insert_with(|| {
+                their_calls += 1;
+                value
+            }) += 1;
+            assert_eq!(Option::from(ours), theirs);
+            assert_eq!(our_calls, their_calls);
+        });
+    }
+
+    #[test]
+    fn iterators() {
+        check(|a, _| {
+            let ours = my(a);
+            assert!(ours.iter().eq(a.iter()));
+            assert!((&ours).into_iter().eq(&a));
+            assert!(ours.into_iter().eq(a));
+        });
+    }
+}
@@ -5,7 +5,7 @@
 fn main() {
     let mut not_null: i32 = 42;
     not_null = 43;
-    // not_null = None; // This won't compile because it's a different type!
+    // not_null = None; // This won't compile (E0308) because it's a different type!
 
     let mut nullable: Option<i32> = Some(42);
     nullable = None;
@@ -13,7 +13,7 @@ fn main() {
 
     // Such construction is rare, but possible.
     let mut double_nullable: Option<Option<i32>> = Some(Some(42));
-    // This won't even compile because it's a different type.
+    // This won't even compile (E0308) because it's a different type.
     // assert_ne!(double_nullable, Some(42));
     double_nullable = None;
     double_nullable = Some(None);
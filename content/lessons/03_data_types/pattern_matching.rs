@@ -1,9 +1,14 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+mod expr;
+mod random;
+
+use rand::Rng;
+
 fn main() {
     // Pattern matching is basically a switch on steroids.
-    let number = rand::random::<i32>();
+    let number: i32 = random::rng().gen();
     match number % 7 {
         0 => println!("{number} is divisible by 7"),
         1 => println!("{number} is *almost* divisible by 7"),
@@ -128,4 +133,7 @@ fn main() {
         "Tastes do change with time and John likes {:?} now.",
         john.favorite_color
     );
+
+    // Patterns really shine on recursive data, like the expressions in `expr.rs`.
+    expr::show();
 }
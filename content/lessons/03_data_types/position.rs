@@ -0,0 +1,314 @@
+// Operators and movement for `Position`, in a module declared with `mod position;`.
+// The struct is defined in `data_types.rs`, but its `impl` blocks can live in any module.
+use super::Position;
+use std::collections::{BTreeSet, HashSet};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+// Operators are traits too. The arithmetic panics on overflow, in release builds as well,
+// instead of silently wrapping around to the other side of the map.
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, other: Position) -> Position {
+        self.checked_add(other).expect("position overflow")
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, other: Position) -> Position {
+        let x = self.0.checked_sub(other.0);
+        let y = self.1.checked_sub(other.1);
+        Position(x.expect("position overflow"), y.expect("position overflow"))
+    }
+}
+
+impl Neg for Position {
+    type Output = Position;
+
+    fn neg(self) -> Position {
+        Position(0, 0) - self
+    }
+}
+
+// `+=` comes from a separate trait, it doesn't follow from `+`.
+impl AddAssign for Position {
+    fn add_assign(&mut self, other: Position) {
+        *self = *self + other;
+    }
+}
+
+// Scaling by a number: `Position * i32`, but not `i32 * Position` - that would be another impl.
+impl Mul<i32> for Position {
+    type Output = Position;
+
+    fn mul(self, factor: i32) -> Position {
+        let x = self.0.checked_mul(factor);
+        let y = self.1.checked_mul(factor);
+        Position(x.expect("position overflow"), y.expect("position overflow"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    // `y` grows upwards, like in maths.
+    fn offset(self) -> Position {
+        match self {
+            Direction::Up => Position(0, 1),
+            Direction::Down => Position(0, -1),
+            Direction::Left => Position(-1, 0),
+            Direction::Right => Position(1, 0),
+        }
+    }
+}
+
+impl Position {
+    fn checked_add(self, other: Position) -> Option<Position> {
+        Some(Position(
+            self.0.checked_add(other.0)?,
+            self.1.checked_add(other.1)?,
+        ))
+    }
+
+    pub fn step(self, direction: Direction) -> Position {
+        self + direction.offset()
+    }
+
+    // The distances are computed in `i64`, because the difference of two `i32`s
+    // doesn't always fit in an `i32`.
+    fn differences(self, other: Position) -> (u64, u64) {
+        // The i-th field of a tuple or a tuple struct can be accessed through 'tuple.i'.
+        // Do not abuse this syntax, though; it's often cleaner to perform
+        // pattern matching to decompose the tuple.
+        let dx = (i64::from(other.0) - i64::from(self.0)).unsigned_abs();
+        let dy = (i64::from(other.1) - i64::from(self.1)).unsigned_abs();
+        (dx, dy)
+    }
+
+    // Moving only along the axes, like a rook moving one field at a time.
+    pub fn manhattan(self, other: Position) -> u64 {
+        let (dx, dy) = self.differences(other);
+        dx + dy
+    }
+
+    // Moving diagonally too, like a king.
+    pub fn chebyshev(self, other: Position) -> u64 {
+        let (dx, dy) = self.differences(other);
+        dx.max(dy)
+    }
+
+    // As the crow flies.
+    pub fn euclidean(self, other: Position) -> f64 {
+        let (dx, dy) = self.differences(other);
+        (dx as f64).hypot(dy as f64)
+    }
+}
+
+// The positions from `(0, 0)` to `(width - 1, height - 1)`.
+// The fields are private, so every grid goes through `new` and has at least one position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Grid {
+    width: i32,
+    height: i32,
+}
+
+impl Grid {
+    pub fn new(width: i32, height: i32) -> Option<Grid> {
+        (width > 0 && height > 0).then_some(Grid { width, height })
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (0..self.width).contains(&position.0) && (0..self.height).contains(&position.1)
+    }
+
+    pub fn clamp(&self, position: Position) -> Position {
+        Position(
+            position.0.clamp(0, self.width - 1),
+            position.1.clamp(0, self.height - 1),
+        )
+    }
+
+    // Rejects moves that would leave the grid.
+    pub fn step(&self, from: Position, direction: Direction) -> Option<Position> {
+        let to = from.checked_add(direction.offset())?;
+        self.contains(to).then_some(to)
+    }
+
+    // Moves as far as the grid allows.
+    pub fn step_clamped(&self, from: Position, direction: Direction, steps: i32) -> Position {
+        let Position(dx, dy) = direction.offset();
+        // Saturating at the limits of `i32` can't bring us back inside the grid.
+        let to = Position(
+            from.0.saturating_add(dx.saturating_mul(steps)),
+            from.1.saturating_add(dy.saturating_mul(steps)),
+        );
+        self.clamp(to)
+    }
+}
+
+pub fn show() {
+    // With the operators implemented, positions can be used like numbers.
+    let mut walker = Position(1, 2) + Position(3, 4) * 2 - Position(1, 1);
+    walker += -Position(1, 1);
+    println!("{:?}", walker);
+    assert_eq!(walker, Position(5, 8));
+    let target = walker.step(Direction::Up).step(Direction::Right);
+    println!(
+        "From {:?} to {:?}: Manhattan {}, Chebyshev {}, Euclidean {:.3}",
+        walker,
+        target,
+        walker.manhattan(target),
+        walker.chebyshev(target),
+        walker.euclidean(target)
+    );
+
+    let grid = Grid::new(10, 10).unwrap();
+    let corner = Position(9, 0);
+    println!("Off the grid: {:?}", grid.step(corner, Direction::Right));
+    println!(
+        "Clamped: {:?}",
+        grid.step_clamped(corner, Direction::Left, 100)
+    );
+
+    // Positions reachable in one step from the corner, in a set.
+    let reachable: BTreeSet<Position> = Direction::ALL
+        .iter()
+        .filter_map(|&direction| grid.step(corner, direction))
+        .collect();
+    println!("Reachable: {:?}", reachable);
+    let visited: HashSet<Position> = [corner, corner, walker].into_iter().collect();
+    assert_eq!(visited.len(), 2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Position(1, -2);
+        let b = Position(-5, 7);
+        assert_eq!(a + b, Position(-4, 5));
+        assert_eq!(a - b, Position(6, -9));
+        assert_eq!(-a, Position(-1, 2));
+        assert_eq!(a * 3, Position(3, -6));
+        let mut c = a;
+        c += b;
+        assert_eq!(c, a + b);
+    }
+
+    #[test]
+    #[should_panic(expected = "position overflow")]
+    fn add_overflows_at_max() {
+        let _ = Position(i32::MAX, 0) + Position(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "position overflow")]
+    fn neg_overflows_at_min() {
+        let _ = -Position(0, i32::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "position overflow")]
+    fn mul_overflows() {
+        let _ = Position(0, i32::MAX / 2 + 1) * 2;
+    }
+
+    #[test]
+    #[should_panic(expected = "position overflow")]
+    fn step_overflows_at_max() {
+        Position(0, i32::MAX).step(Direction::Up);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Position(0, 0);
+        let b = Position(3, -4);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+        assert_eq!(b.manhattan(a), 7);
+
+        // Far more than fits in an `i32`, but no overflow.
+        let min = Position(i32::MIN, i32::MIN);
+        let max = Position(i32::MAX, i32::MAX);
+        assert_eq!(min.manhattan(max), 2 * u64::from(u32::MAX));
+        assert_eq!(min.chebyshev(max), u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn empty_grids_are_rejected() {
+        assert_eq!(Grid::new(0, 5), None);
+        assert_eq!(Grid::new(5, -1), None);
+        assert_eq!(Grid::new(i32::MIN, i32::MIN), None);
+        let single = Grid::new(1, 1).unwrap();
+        assert_eq!(single.clamp(Position(-7, 9)), Position(0, 0));
+    }
+
+    #[test]
+    fn grid_rejects_and_clamps() {
+        let grid = Grid::new(3, 2).unwrap();
+        let corner = Position(2, 1);
+        assert_eq!(grid.step(corner, Direction::Up), None);
+        assert_eq!(grid.step(corner, Direction::Right), None);
+        assert_eq!(grid.step(corner, Direction::Left), Some(Position(1, 1)));
+        assert_eq!(
+            grid.step_clamped(corner, Direction::Left, 5),
+            Position(0, 1)
+        );
+        assert_eq!(
+            grid.step_clamped(corner, Direction::Down, 1),
+            Position(2, 0)
+        );
+        assert_eq!(grid.clamp(Position(-7, 9)), Position(0, 1));
+    }
+
+    #[test]
+    fn grid_at_the_limits_of_i32() {
+        let grid = Grid::new(i32::MAX, i32::MAX).unwrap();
+        let edge = Position(i32::MAX - 1, 0);
+        assert_eq!(grid.step(edge, Direction::Right), None);
+        assert_eq!(grid.step(Position(i32::MAX, 0), Direction::Right), None);
+        assert_eq!(
+            grid.step_clamped(edge, Direction::Right, i32::MAX),
+            Position(i32::MAX - 1, 0)
+        );
+        assert_eq!(
+            grid.step_clamped(edge, Direction::Left, i32::MAX),
+            Position(0, 0)
+        );
+    }
+
+    #[test]
+    fn positions_as_set_keys() {
+        let path = [
+            Position(1, 1),
+            Position(0, 2),
+            Position(1, 1),
+            Position(0, -1),
+        ];
+        let unique: HashSet<Position> = path.into_iter().collect();
+        assert_eq!(unique.len(), 3);
+        let sorted: BTreeSet<Position> = path.into_iter().collect();
+        assert_eq!(
+            sorted.into_iter().collect::<Vec<_>>(),
+            [Position(0, -1), Position(0, 2), Position(1, 1)]
+        );
+    }
+}
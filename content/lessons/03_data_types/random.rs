@@ -0,0 +1,11 @@
+// A module shared by the examples of this lesson, declared with `mod random;` in each of them.
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Set the `LESSONS_SEED` environment variable to always get the same numbers.
+pub fn rng() -> StdRng {
+    match std::env::var("LESSONS_SEED") {
+        Ok(seed) => StdRng::seed_from_u64(seed.parse().expect("LESSONS_SEED must be a number")),
+        Err(_) => StdRng::from_entropy(),
+    }
+}
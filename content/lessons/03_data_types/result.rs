@@ -1,59 +1,313 @@
-#![allow(dead_code)]
-#![allow(unused_variables)]
-
-use std::fs::File;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
 use std::io;
 use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::string::FromUtf8Error;
 
-// Let's try reading from a file.
+// Let's try reading the first `count` bytes of a file.
 // Obviously this can fail.
-fn first_try() -> io::Result<String> {
-    let file = File::open("/dev/random");
+// Nothing calls this version, it's only here to compare with `second_try`.
+#[allow(dead_code)]
+fn first_try(path: &Path, count: usize) -> io::Result<Vec<u8>> {
+    let file = File::open(path);
     match file {
         Ok(mut file) => {
             // We got a file!
-            let mut buffer = vec![0; 128];
+            let mut buffer = vec![0; count];
             // Matching each result quickly become tedious...
-            // Later in this file there's syntactic sugar to make it cleaner.
+            // `second_try` below uses syntactic sugar to make it cleaner.
             match file.read_exact(&mut buffer) {
-                Ok(_) => {
-                    let gibberish = String::from_utf8_lossy(&buffer);
-                    Ok(gibberish.to_string())
-                }
+                Ok(_) => Ok(buffer),
                 Err(error) => Err(error),
             }
         }
         Err(error) => {
             // This is needed in order to change the type from
-            // `io::Result<File>` to `io::Result<()>`.
+            // `io::Result<File>` to `io::Result<Vec<u8>>`.
             Err(error)
         }
     }
 }
 
-// The '?' operator allows us to return early in case of an error
+// The same as `first_try`. The '?' operator allows us to return early in case of an error
 // (it automatically converts the error type).
-fn second_try(filename: &'static str) -> io::Result<String> {
-    let mut file = File::open(filename)?;
-    let mut buffer = vec![0; 128];
+fn second_try(path: &Path, count: usize) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; count];
     file.read_exact(&mut buffer)?;
-    let gibberish = String::from_utf8_lossy(&buffer);
-    Ok(gibberish.to_string())
+    Ok(buffer)
 }
 
-fn main() {
-    let filenames = [
-        "/dev/random",
-        "/dev/null",
-        "/dev/cpu",
-        "/dev/fuse",
-        "there_certainly_is_no_such_file",
+// `third_try` turns the bytes from `second_try` into text.
+// `from_utf8_lossy` quietly replaces invalid bytes with `�`.
+// Sometimes we'd rather know, so let's make it an error too.
+// Now reading can fail in two different ways, so we need an error type that can hold either.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    InvalidUtf8(FromUtf8Error),
+}
+
+// The message says what went wrong on our level, the wrapped error tells why.
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(_) => write!(f, "can't read the file"),
+            ReadError::InvalidUtf8(_) => write!(f, "the file is not valid UTF-8"),
+        }
+    }
+}
+
+// `source` gives access to the wrapped error, so that the whole chain can be reported.
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::InvalidUtf8(error) => Some(error),
+        }
+    }
+}
+
+// These let `?` convert the errors automatically.
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> ReadError {
+        ReadError::Io(error)
+    }
+}
+
+impl From<FromUtf8Error> for ReadError {
+    fn from(error: FromUtf8Error) -> ReadError {
+        ReadError::InvalidUtf8(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Utf8Mode {
+    // Invalid bytes are replaced with `�`.
+    Lossy,
+    // Invalid bytes are an error.
+    Strict,
+}
+
+// `?` turns the `io::Error` from `second_try` into a `ReadError`, using the `From` impl above.
+fn third_try(path: &Path, count: usize, mode: Utf8Mode) -> Result<String, ReadError> {
+    let buffer = second_try(path, count)?;
+    let text = match mode {
+        Utf8Mode::Lossy => String::from_utf8_lossy(&buffer).into_owned(),
+        Utf8Mode::Strict => String::from_utf8(buffer)?,
+    };
+    Ok(text)
+}
+
+// Prints an error, followed by everything that caused it.
+fn report(error: &dyn Error) {
+    println!("Error: {error}");
+    let mut source = error.source();
+    while let Some(cause) = source {
+        println!("  caused by: {cause}");
+        source = cause.source();
+    }
+}
+
+fn read_and_report(name: &str, path: &Path, count: usize, mode: Utf8Mode) {
+    println!("Trying to read {count} bytes from '{name}'");
+    match third_try(path, count, mode) {
+        Ok(text) => println!("{text}"),
+        Err(error) => report(&error),
+    }
+}
+
+const USAGE: &str = "Usage: result [--strict] [--bytes COUNT] FILE...";
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    mode: Utf8Mode,
+    count: usize,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        mode: Utf8Mode::Lossy,
+        count: 128,
+        paths: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--strict" => parsed.mode = Utf8Mode::Strict,
+            "--bytes" => {
+                let count = args.next().ok_or("--bytes needs a number")?;
+                parsed.count = count
+                    .parse()
+                    .map_err(|error| format!("invalid byte count '{count}': {error}"))?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{arg}'")),
+            _ => parsed.paths.push(PathBuf::from(arg)),
+        }
+    }
+    Ok(parsed)
+}
+
+// A file that deletes itself when dropped, so that the examples leave nothing behind.
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> io::Result<TempFile> {
+        // The process id keeps programs running at the same time from using the same files.
+        let path = std::env::temp_dir().join(format!("lessons-{}-{name}", std::process::id()));
+        fs::write(&path, contents)?;
+        Ok(TempFile { path })
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// Reads from files made up on the spot, so that the output is always the same.
+fn demo() {
+    let pangram = TempFile::new("pangram.txt", "Zażółć gęślą jaźń".as_bytes()).unwrap();
+    let binary = TempFile::new("binary.bin", b"\xc0\xffhello, world!").unwrap();
+    let missing = std::env::temp_dir().join("there_certainly_is_no_such_file");
+    let files = [
+        ("pangram.txt", pangram.path.as_path()),
+        ("binary.bin", binary.path.as_path()),
+        ("there_certainly_is_no_such_file", missing.as_path()),
     ];
-    for filename in filenames {
-        println!("Trying to read from '{}'", filename);
-        match second_try(filename) {
-            Ok(gibberish) => println!("{}", gibberish),
-            Err(error) => println!("Error: {}", error),
+    for mode in [Utf8Mode::Lossy, Utf8Mode::Strict] {
+        println!("=== {mode:?}");
+        for (name, path) in files {
+            read_and_report(name, path, 15, mode);
+        }
+        // The pangram is shorter than that.
+        read_and_report("pangram.txt", &pangram.path, 128, mode);
+    }
+}
+
+fn main() {
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{error}\n{USAGE}");
+        std::process::exit(1);
+    });
+    if args.paths.is_empty() {
+        println!("{USAGE}");
+        println!("No files given, let's read some examples.");
+        demo();
+        return;
+    }
+    for path in &args.paths {
+        read_and_report(&path.to_string_lossy(), path, args.count, args.mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn reads_valid_text_in_both_modes() {
+        let file = TempFile::new("test-valid.txt", "Zażółć gęślą jaźń".as_bytes()).unwrap();
+        for mode in [Utf8Mode::Lossy, Utf8Mode::Strict] {
+            assert_eq!(third_try(&file.path, 10, mode).unwrap(), "Zażółć");
         }
     }
+
+    #[test]
+    fn strict_mode_rejects_invalid_utf8() {
+        let file = TempFile::new("test-invalid.bin", b"\xc0\xffhi").unwrap();
+        assert_eq!(
+            third_try(&file.path, 4, Utf8Mode::Lossy).unwrap(),
+            "\u{fffd}\u{fffd}hi"
+        );
+        let error = third_try(&file.path, 4, Utf8Mode::Strict).unwrap_err();
+        let ReadError::InvalidUtf8(cause) = &error else {
+            panic!("expected invalid UTF-8, got {error:?}");
+        };
+        assert_eq!(cause.as_bytes(), b"\xc0\xffhi");
+        assert_eq!(error.to_string(), "the file is not valid UTF-8");
+        assert!(error.source().unwrap().is::<FromUtf8Error>());
+    }
+
+    #[test]
+    fn a_character_cut_in_half_is_invalid() {
+        let file = TempFile::new("test-cut.txt", "gęś".as_bytes()).unwrap();
+        assert_eq!(
+            third_try(&file.path, 2, Utf8Mode::Lossy).unwrap(),
+            "g\u{fffd}"
+        );
+        assert!(matches!(
+            third_try(&file.path, 2, Utf8Mode::Strict),
+            Err(ReadError::InvalidUtf8(_))
+        ));
+    }
+
+    #[test]
+    fn io_errors_are_wrapped() {
+        let missing = std::env::temp_dir().join("lessons-test-there-is-no-such-file");
+        let error = third_try(&missing, 1, Utf8Mode::Strict).unwrap_err();
+        assert!(matches!(&error, ReadError::Io(cause) if cause.kind() == io::ErrorKind::NotFound));
+        assert!(error.source().unwrap().is::<io::Error>());
+
+        let short = TempFile::new("test-short.txt", b"abc").unwrap();
+        let error = third_try(&short.path, 4, Utf8Mode::Lossy).unwrap_err();
+        assert!(
+            matches!(&error, ReadError::Io(cause) if cause.kind() == io::ErrorKind::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn both_tries_read_the_same() {
+        let file = TempFile::new("test-tries.txt", b"hello, world!").unwrap();
+        assert_eq!(first_try(&file.path, 5).unwrap(), b"hello");
+        assert_eq!(second_try(&file.path, 5).unwrap(), b"hello");
+        let missing = std::env::temp_dir().join("lessons-test-there-is-no-such-file");
+        assert_eq!(
+            first_try(&missing, 1).unwrap_err().kind(),
+            second_try(&missing, 1).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn temp_files_clean_up() {
+        let file = TempFile::new("test-cleanup.txt", b"bye").unwrap();
+        let path = file.path.clone();
+        assert!(path.exists());
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(
+            args(&["a.txt", "--strict", "--bytes", "16", "b.txt"]),
+            Ok(Args {
+                mode: Utf8Mode::Strict,
+                count: 16,
+                paths: vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")],
+            })
+        );
+        assert_eq!(
+            args(&[]),
+            Ok(Args {
+                mode: Utf8Mode::Lossy,
+                count: 128,
+                paths: Vec::new(),
+            })
+        );
+        assert_eq!(args(&["--bytes"]), Err("--bytes needs a number".to_owned()));
+        assert!(args(&["--bytes", "-1"])
+            .unwrap_err()
+            .starts_with("invalid byte count '-1'"));
+        assert_eq!(args(&["--loud"]), Err("unknown option '--loud'".to_owned()));
+    }
 }
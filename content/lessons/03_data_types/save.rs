@@ -0,0 +1,174 @@
+// Saving and loading the hero, in a module declared with `mod save;` in `data_types.rs`.
+// More `impl` blocks for types defined elsewhere: `Hero` in the parent, `Item` next door.
+use super::inventory::{Armour, Item, Weapon};
+use super::{Hero, Position};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum LoadError {
+    Missing(&'static str),
+    // The 1-based number of the line that couldn't be understood.
+    Invalid(usize),
+    // Enough experience for the next level, which the hero should have reached already.
+    TooMuchExperience,
+}
+
+impl Item {
+    fn to_line(&self) -> String {
+        match self {
+            Item::Weapon(Weapon { name, damage }) => format!("weapon {damage} {name}"),
+            Item::Armour(Armour { name, defence }) => format!("armour {defence} {name}"),
+            Item::Potion { name, healing } => format!("potion {healing} {name}"),
+        }
+    }
+
+    fn from_line(line: &str) -> Option<Item> {
+        let (kind, rest) = line.split_once(' ')?;
+        let (amount, name) = rest.split_once(' ')?;
+        let (amount, name) = (amount.parse().ok()?, name.to_owned());
+        match kind {
+            "weapon" => Some(Item::Weapon(Weapon {
+                name,
+                damage: amount,
+            })),
+            "armour" => Some(Item::Armour(Armour {
+                name,
+                defence: amount,
+            })),
+            "potion" => Some(Item::Potion {
+                name,
+                healing: amount,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Hero {
+    // One `key value` line per field, and one line per item. Names have to fit in a line.
+    pub fn save(&self) -> String {
+        let mut lines = vec![
+            format!("name {}", self.name),
+            format!("level {}", self.level),
+            format!("experience {}", self.experience),
+            format!("position {} {}", self.position.0, self.position.1),
+        ];
+        lines.extend(self.inventory.bag.iter().map(Item::to_line));
+        if let Some(Weapon { name, damage }) = &self.inventory.weapon {
+            lines.push(format!("equipped weapon {damage} {name}"));
+        }
+        if let Some(Armour { name, defence }) = &self.inventory.armour {
+            lines.push(format!("equipped armour {defence} {name}"));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    pub fn load(text: &str) -> Result<Hero, LoadError> {
+        if !text.lines().any(|line| line.starts_with("name ")) {
+            return Err(LoadError::Missing("name"));
+        }
+        let mut hero = Hero::new(String::new());
+        for (i, line) in text.lines().enumerate() {
+            hero.load_line(line).ok_or(LoadError::Invalid(i + 1))?;
+        }
+        // Otherwise `level_up` would underflow.
+        let needed = Hero::experience_to_next_level(hero.level);
+        if needed.is_none_or(|needed| hero.experience >= needed) {
+            return Err(LoadError::TooMuchExperience);
+        }
+        Ok(hero)
+    }
+
+    // Anything wrong makes the whole line invalid, so an `Option` is enough to say so.
+    // `?` works with `Option` too, returning `None` early.
+    fn load_line(&mut self, line: &str) -> Option<()> {
+        let (key, value) = line.split_once(' ')?;
+        match key {
+            "name" => self.name = value.to_owned(),
+            // `ok()` turns a `Result` into an `Option`, dropping the error.
+            "level" => {
+                let level = value.parse().ok()?;
+                // There's no level 0, and the levels past the highest one can't be reached.
+                Hero::experience_to_next_level(level).filter(|_| level > 0)?;
+                self.level = level;
+            }
+            "experience" => self.experience = value.parse().ok()?,
+            "position" => {
+                let (x, y) = value.split_once(' ')?;
+                self.position = Position(x.parse().ok()?, y.parse().ok()?);
+            }
+            "equipped" => match Item::from_line(value)? {
+                Item::Weapon(weapon) => self.inventory.weapon = Some(weapon),
+                Item::Armour(armour) => self.inventory.armour = Some(armour),
+                Item::Potion { .. } => return None,
+            },
+            _ => self.inventory.add(Item::from_line(line)?),
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut hero = Hero::new(String::from("Ferris the Crab"));
+        hero.gain_experience(1234);
+        hero.position = Position(-3, 7);
+        hero.inventory.add(Item::Weapon(Weapon {
+            name: String::from("Sword"),
+            damage: 4,
+        }));
+        hero.inventory.add(Item::Armour(Armour {
+            name: String::from("Shell"),
+            defence: 9,
+        }));
+        hero.inventory.add(Item::Potion {
+            name: String::from("Sea water"),
+            healing: 1,
+        });
+        hero.inventory.equip(1).unwrap();
+
+        assert_eq!(Hero::load(&hero.save()), Ok(hero.clone()));
+        let fresh = Hero::new(String::new());
+        assert_eq!(Hero::load(&fresh.save()), Ok(fresh));
+    }
+
+    #[test]
+    fn load_reports_problems() {
+        assert_eq!(Hero::load("level 2\n"), Err(LoadError::Missing("name")));
+        assert_eq!(
+            Hero::load("name Ferris\nlevel two\n"),
+            Err(LoadError::Invalid(2))
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nequipped potion 5 Potion\n"),
+            Err(LoadError::Invalid(2))
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nshield 5 Wood\n"),
+            Err(LoadError::Invalid(2))
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nexperience 500\n"),
+            Err(LoadError::TooMuchExperience)
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nlevel 0\n"),
+            Err(LoadError::Invalid(2))
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nlevel 4294967295\n"),
+            Err(LoadError::Invalid(2))
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nlevel 42949672\n").map(|hero| hero.level),
+            Ok(42949672)
+        );
+        assert_eq!(
+            Hero::load("name Ferris\nexperience 199\nlevel 2\n").map(|hero| hero.experience),
+            Ok(199)
+        );
+    }
+}
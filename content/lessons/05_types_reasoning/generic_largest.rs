@@ -1,3 +1,4 @@
+// ANCHOR: largest
 fn largest<T: PartialOrd + Copy>(list: &[T]) -> T {
     let mut largest = list[0];
 
@@ -9,6 +10,7 @@ fn largest<T: PartialOrd + Copy>(list: &[T]) -> T {
 
     largest
 }
+// ANCHOR_END: largest
 
 fn main() {
     let number_list = vec![34, 50, 25, 100, 65];
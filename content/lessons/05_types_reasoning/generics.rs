@@ -85,10 +85,10 @@ fn main() {
     println!("{:?}", arr2);
 
     let arr3 = erasing_cloning_machine1(&arr);
-    // arr3[0]; // won't compile: cannot index into a value of type `impl std::clone::Clone + std::fmt::Debug`
+    // arr3[0]; // won't compile (E0608): cannot index into a value of type `impl std::clone::Clone + std::fmt::Debug`
     println!("{:?}", arr3);
 
     let arr4 = erasing_cloning_machine2(&arr);
-    // arr4[0]; // won't compile: cannot index into a value of type `impl std::clone::Clone + std::fmt::Debug`
+    // arr4[0]; // won't compile (E0608): cannot index into a value of type `impl std::clone::Clone + std::fmt::Debug`
     println!("{:?}", arr4);
 }
@@ -15,7 +15,7 @@ fn main() {
         println!("The longest string is {}", result);
     }
 
-    // This doesn't compile - incorrect lifetimes
+    // This doesn't compile (E0597) - incorrect lifetimes
     //
     // let string1 = String::from("long string is long");
     // let result;
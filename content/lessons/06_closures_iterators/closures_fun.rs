@@ -35,7 +35,7 @@ fn main() {
     for fn_once_callable in fn_once_callables {
         // Cannot move a value of type `dyn FnOnce() -> String`.
         // The size of `dyn FnOnce() -> String` cannot be statically determined.
-        // println!("{}", fn_once_callable());
+        // println!("{}", fn_once_callable()); // won't compile (E0161)
 
         // So, for FnOnce, we need to be their owners to be able to call them,
         // and we can't have a `dyn` object owned on stack.
@@ -15,6 +15,6 @@ fn main() {
         
         // This does not compile, because closures are not generic.
         // Their type is inferred once and stays the same.
-        // let _res = add_one_v4(0_i32);
+        // let _res = add_one_v4(0_i32); // does not compile (E0308)
     };
 }
@@ -3,7 +3,7 @@
 #![allow(dead_code)]
 
 /* unsafe superpower 1: dereferencing pointers. */
-fn superpower_1() {
+fn superpower_1() -> i32 {
     let x = 42;
 
     // Implicit &T -> *const T conversion.
@@ -16,19 +16,21 @@ fn superpower_1() {
     let raw_ptr: *const i32 = &raw const x;
 
     // Dereferencing a raw pointer requires an `unsafe` block.
-    println!("Value: {}", unsafe { *raw_ptr });
+    let value = unsafe { *raw_ptr };
+    println!("Value: {}", value);
+    value
 }
 
 /* unsafe superpower 2: calling an unsafe function. */
-unsafe fn unsafe_function() {
-    println!("This is an unsafe function!");
+unsafe fn unsafe_function() -> &'static str {
+    "This is an unsafe function!"
 }
 
-fn superpower_2() {
-    unsafe {
-        // Calling an unsafe function.
-        unsafe_function();
-    }
+fn superpower_2() -> &'static str {
+    // Calling an unsafe function.
+    let message = unsafe { unsafe_function() };
+    println!("{}", message);
+    message
 }
 
 /* unsafe superpower 3: Accessing or modifying mutable static variable.
@@ -37,18 +39,20 @@ fn superpower_2() {
 
 static mut COUNTER: i32 = 0;
 
-fn increment_counter() {
+fn increment_counter() -> i32 {
     unsafe {
         // Accessing and modifying a mutable static variable
         COUNTER += 1;
-        println!("Counter: {}", COUNTER);
+        let counter = COUNTER;
+        println!("Counter: {}", counter);
+        counter
     }
 }
 
-fn superpower_3() {
+fn superpower_3() -> i32 {
     // This would cause UB: a data race.
     // std::thread::spawn(increment_counter);
-    increment_counter();
+    increment_counter()
 }
 
 /* unsafe superpower 4: Implementing unsafe traits.
@@ -56,25 +60,29 @@ fn superpower_3() {
  * is implemented for a type that should not implement it (think Send/Sync).
  * */
 
+/// # Safety
+///
+/// Implement this trait only for types that really can be accessed atomically.
 unsafe trait CanBeAtomic {
-    fn safe_method_of_unsafe_trait(&self);
+    fn safe_method_of_unsafe_trait(&self) -> i32;
 }
 
 struct MyStruct {
     i: i32,
 }
 
-unsafe impl UnsafeTrait for MyStruct {
-    fn safe_method_of_unsafe_trait(&self) {
+unsafe impl CanBeAtomic for MyStruct {
+    fn safe_method_of_unsafe_trait(&self) -> i32 {
         println!("Method called!");
+        self.i
     }
 }
 
-fn superpower_4() {
+fn superpower_4() -> i32 {
     let my_struct = MyStruct { i: 42 };
 
     // Calling a safe method from an unsafe trait
-    my_struct.safe_method_of_unsafe_trait();
+    my_struct.safe_method_of_unsafe_trait()
 }
 
 /* unsafe superpower 5: Accessing fields of a union.
@@ -87,7 +95,7 @@ union MyUnion {
     bool_value: bool,
 }
 
-fn main() {
+fn superpower_5() -> i32 {
     let u = MyUnion { int_value: 42 };
 
     unsafe {
@@ -96,5 +104,75 @@ fn main() {
 
         // Would result in UB, as the compiler may assume that bool is either 0 or 1 underneath.
         // println!("Union value as bool: {}", u.bool_value);
+
+        u.int_value
+    }
+}
+
+fn run_superpower(number: u32) {
+    match number {
+        1 => {
+            superpower_1();
+        }
+        2 => {
+            superpower_2();
+        }
+        3 => {
+            superpower_3();
+        }
+        4 => {
+            superpower_4();
+        }
+        5 => {
+            superpower_5();
+        }
+        _ => unreachable!(),
+    }
+}
+
+// Run a single superpower with `cargo run --bin 16_unsafe_superpowers -- <1-5>`,
+// or all of them when no number is given.
+fn main() {
+    match std::env::args().nth(1) {
+        None => (1..=5).for_each(run_superpower),
+        Some(arg) => match arg.parse() {
+            Ok(number @ 1..=5) => run_superpower(number),
+            _ => {
+                eprintln!("Usage: 16_unsafe_superpowers [1-5]");
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dereferencing_a_raw_pointer() {
+        assert_eq!(superpower_1(), 42);
+    }
+
+    #[test]
+    fn calling_an_unsafe_function() {
+        assert_eq!(superpower_2(), "This is an unsafe function!");
+    }
+
+    #[test]
+    fn modifying_a_mutable_static() {
+        // This is the only test touching `COUNTER`, so there is no data race.
+        let before = superpower_3();
+        assert_eq!(superpower_3(), before + 1);
+    }
+
+    #[test]
+    fn implementing_an_unsafe_trait() {
+        assert_eq!(superpower_4(), 42);
+    }
+
+    #[test]
+    fn reading_a_union_field() {
+        assert_eq!(superpower_5(), 42);
     }
 }
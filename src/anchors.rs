@@ -0,0 +1,135 @@
+//! Named regions of lesson sources, so that a page can embed a part of a file.
+//!
+//! A region is marked with a pair of comments:
+//!
+//! ```text
+//! // ANCHOR: largest
+//! fn largest<T: PartialOrd + Copy>(list: &[T]) -> T { ... }
+//! // ANCHOR_END: largest
+//! ```
+//!
+//! Regions may overlap and nest. The marker lines themselves never show up on the website,
+//! neither in a region nor in the full file.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+const START: &str = "ANCHOR:";
+const END: &str = "ANCHOR_END:";
+
+/// A source file split into its regions.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Anchored {
+    /// The whole file without the marker lines.
+    pub stripped: String,
+    /// The contents of each region, without marker lines and common indentation.
+    pub regions: BTreeMap<String, String>,
+}
+
+/// A malformed marker. The line numbers are 1-based.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    Duplicate { name: String, line: usize },
+    Unopened { name: String, line: usize },
+    Unclosed { name: String, line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Duplicate { name, line } => {
+                write!(f, "line {line}: anchor `{name}` is opened again")
+            }
+            Error::Unopened { name, line } => {
+                write!(
+                    f,
+                    "line {line}: anchor `{name}` is closed, but was never opened"
+                )
+            }
+            Error::Unclosed { name, line } => {
+                write!(f, "line {line}: anchor `{name}` is never closed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+enum Marker<'a> {
+    Start(&'a str),
+    End(&'a str),
+}
+
+fn marker(line: &str) -> Option<Marker<'_>> {
+    let comment = line.trim().strip_prefix("//")?.trim();
+    if let Some(name) = comment.strip_prefix(END) {
+        Some(Marker::End(name.trim()))
+    } else {
+        comment
+            .strip_prefix(START)
+            .map(|name| Marker::Start(name.trim()))
+    }
+}
+
+/// Whether the source contains any anchor markers at all.
+pub fn has_anchors(source: &str) -> bool {
+    source.lines().any(|line| marker(line).is_some())
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| format!("{}\n", line.get(indent..).unwrap_or_default()))
+        .collect()
+}
+
+pub fn parse(source: &str) -> Result<Anchored, Error> {
+    let mut stripped = String::new();
+    // Name of the region -> (line it was opened at, its lines so far).
+    let mut open: BTreeMap<&str, (usize, Vec<&str>)> = BTreeMap::new();
+    let mut regions = BTreeMap::new();
+
+    for (i, line) in source.lines().enumerate() {
+        match marker(line) {
+            Some(Marker::Start(name)) => {
+                if open.contains_key(name) || regions.contains_key(name) {
+                    return Err(Error::Duplicate {
+                        name: name.to_owned(),
+                        line: i + 1,
+                    });
+                }
+                open.insert(name, (i + 1, Vec::new()));
+            }
+            Some(Marker::End(name)) => {
+                let Some((_, lines)) = open.remove(name) else {
+                    return Err(Error::Unopened {
+                        name: name.to_owned(),
+                        line: i + 1,
+                    });
+                };
+                regions.insert(name.to_owned(), dedent(&lines));
+            }
+            None => {
+                stripped.push_str(line);
+                stripped.push('\n');
+                for (_, lines) in open.values_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+
+    match open.into_iter().next() {
+        Some((name, (line, _))) => Err(Error::Unclosed {
+            name: name.to_owned(),
+            line,
+        }),
+        None => Ok(Anchored { stripped, regions }),
+    }
+}
@@ -0,0 +1,182 @@
+//! Generates the files loaded by `include_code_sample(path=..., anchor=...)`.
+//!
+//! For every `.rs` file under `content/` that contains anchor markers (see `lessons::anchors`),
+//! writes to `static/code/` a copy of the file without the markers (offered for download)
+//! and one file per region, e.g. `generic_largest.largest.rs`.
+//! Fails if a page references an anchor that doesn't exist.
+//!
+//! ```text
+//! cargo run --bin anchors             # regenerate static/code
+//! cargo run --bin anchors -- --check  # only check that static/code is up to date
+//! ```
+
+use lessons::anchors;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const CONTENT_DIR: &str = "content";
+const OUTPUT_DIR: &str = "static/code";
+
+fn collect_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, extension, files);
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            files.push(path);
+        }
+    }
+}
+
+/// Path relative to `base` with forward slashes, as used in shortcodes.
+fn relative(path: &Path, base: &Path) -> String {
+    let relative = path.strip_prefix(base).unwrap();
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Value of a `key="value"` shortcode argument.
+fn shortcode_arg<'a>(call: &'a str, key: &str) -> Option<&'a str> {
+    let start = call.find(&format!("{key}=\""))? + key.len() + 2;
+    let len = call[start..].find('"')?;
+    Some(&call[start..start + len])
+}
+
+/// Contents of all generated files, keyed by their path relative to `OUTPUT_DIR`,
+/// together with the anchors of every source file, keyed by its path relative to `CONTENT_DIR`.
+#[allow(clippy::type_complexity)]
+fn generate(
+    content: &Path,
+    errors: &mut Vec<String>,
+) -> (BTreeMap<String, String>, BTreeMap<String, Vec<String>>) {
+    let mut sources = Vec::new();
+    collect_files(content, "rs", &mut sources);
+
+    let mut outputs = BTreeMap::new();
+    let mut anchors_by_file = BTreeMap::new();
+    for source_path in sources {
+        let source = fs::read_to_string(&source_path).unwrap();
+        if !anchors::has_anchors(&source) {
+            continue;
+        }
+        let path = relative(&source_path, content);
+        let anchored = match anchors::parse(&source) {
+            Ok(anchored) => anchored,
+            Err(error) => {
+                errors.push(format!("{CONTENT_DIR}/{path}: {error}"));
+                continue;
+            }
+        };
+
+        let stem = path.trim_end_matches(".rs");
+        for (name, region) in &anchored.regions {
+            outputs.insert(format!("{stem}.{name}.rs"), region.clone());
+        }
+        anchors_by_file.insert(path.clone(), anchored.regions.into_keys().collect());
+        outputs.insert(path, anchored.stripped);
+    }
+
+    (outputs, anchors_by_file)
+}
+
+/// Checks that every anchor referenced by `include_code_sample` exists.
+fn check_references(
+    content: &Path,
+    anchors_by_file: &BTreeMap<String, Vec<String>>,
+    errors: &mut Vec<String>,
+) {
+    let mut pages = Vec::new();
+    collect_files(content, "md", &mut pages);
+    pages.sort();
+
+    for page in pages {
+        let text = fs::read_to_string(&page).unwrap();
+        for (i, line) in text.lines().enumerate() {
+            let Some(start) = line.find("include_code_sample(") else {
+                continue;
+            };
+            let call = &line[start..];
+            let (Some(path), Some(anchor)) =
+                (shortcode_arg(call, "path"), shortcode_arg(call, "anchor"))
+            else {
+                continue;
+            };
+            let exists = anchors_by_file
+                .get(path)
+                .is_some_and(|anchors| anchors.iter().any(|a| a == anchor));
+            if !exists {
+                errors.push(format!(
+                    "{}:{}: anchor `{anchor}` does not exist in {path}",
+                    relative(&page, content.parent().unwrap()),
+                    i + 1
+                ));
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let check_only = match std::env::args().nth(1).as_deref() {
+        None => false,
+        Some("--check") => true,
+        Some(_) => {
+            eprintln!("Usage: anchors [--check]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let content = root.join(CONTENT_DIR);
+    let output_dir = root.join(OUTPUT_DIR);
+
+    let mut errors = Vec::new();
+    let (outputs, anchors_by_file) = generate(&content, &mut errors);
+    check_references(&content, &anchors_by_file, &mut errors);
+
+    let mut existing = Vec::new();
+    if output_dir.exists() {
+        collect_files(&output_dir, "rs", &mut existing);
+    }
+    let existing: BTreeMap<String, PathBuf> = existing
+        .into_iter()
+        .map(|path| (relative(&path, &output_dir), path))
+        .collect();
+
+    let mut out_of_date = false;
+    for (path, file) in &existing {
+        if !outputs.contains_key(path) {
+            if check_only {
+                out_of_date = true;
+                errors.push(format!("{OUTPUT_DIR}/{path} is stale"));
+            } else {
+                fs::remove_file(file).unwrap();
+            }
+        }
+    }
+    for (path, text) in &outputs {
+        let file = output_dir.join(path);
+        if fs::read_to_string(&file).ok().as_ref() == Some(text) {
+            continue;
+        }
+        if check_only {
+            out_of_date = true;
+            errors.push(format!("{OUTPUT_DIR}/{path} is out of date"));
+        } else {
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(&file, text).unwrap();
+        }
+    }
+
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        if out_of_date {
+            eprintln!("Run `cargo run --bin anchors` to regenerate {OUTPUT_DIR}.");
+        }
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,98 @@
+//! Checks, without going online, that every local file referenced from `content/`
+//! (see `lessons::links`) exists, and that every `.rs` file under `content/` is referenced
+//! by some page, so that no example silently disappears from the website.
+//! The examples from the previous semesters are exempt from the latter,
+//! `tests/archive.rs` keeps track of them instead.
+//!
+//! References to files that are known to be missing are still reported, but don't fail the check.
+//!
+//! ```text
+//! cargo run --bin links
+//! ```
+
+use lessons::links;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const CONTENT_DIR: &str = "content";
+const STATIC_DIR: &str = "static";
+const ARCHIVE_DIR: &str = "content/lessons/old";
+
+/// Assets that were never committed to the repository, as `page: target`.
+const KNOWN_MISSING: &[&str] = &[
+    // The demo video of a student project.
+    "content/lessons/project_showcase/index.md: /lessons/project-showcase/graph_vis_demo.h264",
+];
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let content = root.join(CONTENT_DIR);
+    let static_dir = root.join(STATIC_DIR);
+    let relative = |path: &Path| {
+        let relative = path.strip_prefix(root).unwrap();
+        relative.to_string_lossy().replace('\\', "/")
+    };
+
+    let mut files = Vec::new();
+    collect_files(&content, &mut files);
+    files.sort();
+
+    let mut errors = Vec::new();
+    let mut referenced = BTreeSet::new();
+    for page in files
+        .iter()
+        .filter(|file| file.extension().is_some_and(|e| e == "md"))
+    {
+        for reference in links::extract(&fs::read_to_string(page).unwrap()) {
+            match links::resolve(&content, &static_dir, page, &reference) {
+                Some(path) => {
+                    referenced.insert(path);
+                }
+                None => {
+                    let page = relative(page);
+                    let message = format!(
+                        "{page}:{}: dangling {} `{}`",
+                        reference.line, reference.kind, reference.target
+                    );
+                    if KNOWN_MISSING.contains(&format!("{page}: {}", reference.target).as_str()) {
+                        eprintln!("{message} (known to be missing)");
+                    } else {
+                        errors.push(message);
+                    }
+                }
+            }
+        }
+    }
+
+    let archive = root.join(ARCHIVE_DIR);
+    for file in files
+        .iter()
+        .filter(|file| file.extension().is_some_and(|e| e == "rs"))
+    {
+        if !referenced.contains(file) && !file.starts_with(&archive) {
+            errors.push(format!("{}: not referenced by any page", relative(file)));
+        }
+    }
+
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        ExitCode::FAILURE
+    }
+}
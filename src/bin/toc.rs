@@ -0,0 +1,96 @@
+//! Generates `content/lessons/table_of_contents.md` from the front matter of the lesson pages
+//! and the concept list in `content/lessons/concepts.toml` (see `lessons::toc`).
+//! Fails if the two disagree, e.g. if a lesson was added, moved or renamed without updating
+//! the concept list.
+//!
+//! ```text
+//! cargo run --bin toc             # regenerate the table of contents
+//! cargo run --bin toc -- --check  # only check that it is up to date
+//! ```
+
+use lessons::toc;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+const LESSONS_DIR: &str = "content/lessons";
+const CONCEPTS: &str = "content/lessons/concepts.toml";
+const TABLE_OF_CONTENTS: &str = "content/lessons/table_of_contents.md";
+
+fn read_lessons(lessons_dir: &Path, errors: &mut Vec<String>) -> Vec<toc::Lesson> {
+    let mut lessons = Vec::new();
+    for entry in fs::read_dir(lessons_dir).unwrap() {
+        let path = entry.unwrap().path().join("index.md");
+        let dir = path
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_string_lossy();
+        if !path.exists() || toc::lesson_number(&dir).is_none() {
+            continue;
+        }
+        match toc::parse_lesson(&dir, &fs::read_to_string(&path).unwrap()) {
+            Ok(lesson) => lessons.push(lesson),
+            Err(error) => errors.push(format!("{LESSONS_DIR}/{dir}/index.md: {error}")),
+        }
+    }
+    toc::sort_lessons(&mut lessons);
+    lessons
+}
+
+fn main() -> ExitCode {
+    let check_only = match std::env::args().nth(1).as_deref() {
+        None => false,
+        Some("--check") => true,
+        Some(_) => {
+            eprintln!("Usage: toc [--check]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut errors = Vec::new();
+    let lessons = read_lessons(&root.join(LESSONS_DIR), &mut errors);
+    let concepts = match toc::parse_concepts(&fs::read_to_string(root.join(CONCEPTS)).unwrap()) {
+        Ok(concepts) => concepts,
+        Err(error) => {
+            errors.push(format!("{CONCEPTS}: {error}"));
+            Vec::new()
+        }
+    };
+    if errors.is_empty() {
+        for error in toc::check_concepts(&lessons, &concepts) {
+            errors.push(format!("{CONCEPTS}: {error}"));
+        }
+    }
+
+    let path = root.join(TABLE_OF_CONTENTS);
+    let current = fs::read_to_string(&path).unwrap();
+    if errors.is_empty() {
+        match toc::split_front_matter(&current) {
+            Ok((front_matter, body)) => {
+                let generated = toc::render(&lessons, &concepts);
+                if body != generated {
+                    if check_only {
+                        errors.push(format!(
+                            "{TABLE_OF_CONTENTS} is out of date, run `cargo run --bin toc`"
+                        ));
+                    } else {
+                        fs::write(&path, format!("+++\n{front_matter}+++\n{generated}")).unwrap();
+                    }
+                }
+            }
+            Err(error) => errors.push(format!("{TABLE_OF_CONTENTS}: {error}")),
+        }
+    }
+
+    if errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,156 @@
+//! The lesson examples, listed for the `lessons` launcher (see `src/main.rs`),
+//! and the tools that keep the website's content in order.
+//!
+//! Every example is a standalone binary (see `Cargo.toml`), which is how its source is shown
+//! and downloaded on the website. The launcher runs those binaries rather than linking
+//! the examples in, so that their sources stay as they are shown and their tests run once.
+
+pub mod anchors;
+pub mod links;
+pub mod toc;
+
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Where the lesson directories are, relative to the repository root.
+const LESSONS_DIR: &str = "content/lessons/";
+
+/// A single lesson example.
+pub struct Example {
+    /// The name of the example's binary in `Cargo.toml`.
+    pub name: &'static str,
+    /// Path to the source, relative to the repository root.
+    path: &'static str,
+}
+
+impl Example {
+    /// Path to the source, relative to the repository root.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The lesson directory the example comes from, e.g. `05_types_reasoning`,
+    /// even if it's nested deeper, like `02_ownership/dont_panic/dont_panic.rs`.
+    pub fn lesson(&self) -> &'static str {
+        let path = self.path.strip_prefix(LESSONS_DIR).unwrap_or(self.path);
+        path.split('/').next().unwrap_or_default()
+    }
+
+    /// The example's binary, built by Cargo next to the running executable.
+    pub fn binary(&self) -> io::Result<PathBuf> {
+        let exe = std::env::current_exe()?;
+        Ok(exe.with_file_name(format!("{}{}", self.name, std::env::consts::EXE_SUFFIX)))
+    }
+}
+
+/// How a run of an example went.
+pub struct Run {
+    /// Everything the example printed, its stdout and stderr together, in the order printed.
+    pub output: String,
+    pub status: ExitStatus,
+}
+
+impl Run {
+    /// Whether the example ended with a panic: one that reaches the end of `main`
+    /// makes the process exit with code 101.
+    pub fn panicked(&self) -> bool {
+        self.status.code() == Some(101)
+    }
+}
+
+/// Runs the example's binary the way the snapshot tests do: without arguments and
+/// with an empty stdin, capturing what it prints.
+pub fn run(example: &Example) -> io::Result<Run> {
+    let binary = example.binary()?;
+    if !binary.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "{} is not built, run `cargo build --bins`",
+                binary.display()
+            ),
+        ));
+    }
+    // One pipe for both streams keeps their lines in order.
+    let (mut reader, writer) = io::pipe()?;
+    let mut child = Command::new(binary)
+        .stdin(Stdio::null())
+        .stdout(writer.try_clone()?)
+        .stderr(writer)
+        .spawn()?;
+    // The `Command` is gone, so the child holds the only ends of the pipe for writing,
+    // and reading stops when it exits.
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output)?;
+    Ok(Run {
+        output: String::from_utf8_lossy(&output).into_owned(),
+        status: child.wait()?,
+    })
+}
+
+pub fn find(name: &str) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|example| example.name == name)
+}
+
+macro_rules! examples {
+    ($($name:literal => $path:literal;)*) => {
+        /// All examples, in the order of the lessons.
+        pub static EXAMPLES: &[Example] = &[$(
+            Example {
+                name: $name,
+                path: $path,
+            },
+        )*];
+    };
+}
+
+examples! {
+    "01_hello_world" => "content/lessons/01_introduction/hello_world.rs";
+    "01_conditionals" => "content/lessons/01_introduction/conditionals.rs";
+    "01_functions" => "content/lessons/01_introduction/functions.rs";
+    "01_loops" => "content/lessons/01_introduction/loops.rs";
+    "01_variables" => "content/lessons/01_introduction/variables.rs";
+    "01_guessing_game" => "content/lessons/01_introduction/guessing_game.rs";
+    "01_panic_gallery" => "content/lessons/01_introduction/panic_gallery.rs";
+
+    "02_ownership" => "content/lessons/02_ownership/ownership.rs";
+    "02_words" => "content/lessons/02_ownership/words.rs";
+    "02_string_formatting" => "content/lessons/02_ownership/string_formatting/string_formatting.rs";
+    "02_dont_panic" => "content/lessons/02_ownership/dont_panic/dont_panic.rs";
+    "02_library" => "content/lessons/02_ownership/library.rs";
+
+    "03_data_types" => "content/lessons/03_data_types/data_types.rs";
+    "03_enums" => "content/lessons/03_data_types/enums.rs";
+    "03_enum_layout" => "content/lessons/03_data_types/enum_layout.rs";
+    "03_option" => "content/lessons/03_data_types/option.rs";
+    "03_my_option" => "content/lessons/03_data_types/my_option.rs";
+    "03_pattern_matching" => "content/lessons/03_data_types/pattern_matching.rs";
+    "03_result" => "content/lessons/03_data_types/result.rs";
+
+    "05_basic_traits" => "content/lessons/05_types_reasoning/basic_trait.rs";
+    "05_basic_trait_display" => "content/lessons/05_types_reasoning/basic_trait_display.rs";
+    "05_trait_associated_types" => "content/lessons/05_types_reasoning/trait_associated_type.rs";
+    "05_trait_generic_types" => "content/lessons/05_types_reasoning/trait_generic_type.rs";
+    "05_impl_trait" => "content/lessons/05_types_reasoning/impl_trait.rs";
+    "05_generic_largest" => "content/lessons/05_types_reasoning/generic_largest.rs";
+    "05_generics" => "content/lessons/05_types_reasoning/generics.rs";
+    "05_non_generic" => "content/lessons/05_types_reasoning/non_generic.rs";
+    "05_trait_default" => "content/lessons/05_types_reasoning/trait_default.rs";
+    "05_lifetimes_basic" => "content/lessons/05_types_reasoning/lifetimes_basic.rs";
+    "05_lifetimes_elision" => "content/lessons/05_types_reasoning/lifetimes_elision.rs";
+    "05_generic_fun" => "content/lessons/05_types_reasoning/generics_fun.rs";
+    "05_static_dynamic_dispatch" => "content/lessons/05_types_reasoning/static_dynamic_dispatch.rs";
+
+    "06_closures_syntax" => "content/lessons/06_closures_iterators/closures_syntax.rs";
+    "06_closures_capturing" => "content/lessons/06_closures_iterators/closures_capturing.rs";
+    "06_closures_fun" => "content/lessons/06_closures_iterators/closures_fun.rs";
+    "06_iterator_exhaustion" => "content/lessons/06_closures_iterators/iterator_exhaustion.rs";
+
+    "07_box" => "content/lessons/07_smart_pointers/box.rs";
+    "07_deref_coercion" => "content/lessons/07_smart_pointers/deref_coercion.rs";
+    "07_ref_count" => "content/lessons/07_smart_pointers/ref_count.rs";
+    "07_weak_ref" => "content/lessons/07_smart_pointers/weak_ref.rs";
+
+    "16_unsafe_superpowers" => "content/lessons/16_unsafe/unsafe_superpowers.rs";
+}
@@ -0,0 +1,226 @@
+//! References from the pages to other files: Markdown links and images, `src`/`href` attributes
+//! of inline HTML, and `path` arguments of shortcodes like `include_code_sample`.
+//!
+//! Everything inside code blocks and code spans is ignored, and so are external links
+//! (e.g. `https://...`) and links to a fragment of the same page.
+
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Link,
+    Image,
+    /// A `src` or `href` attribute of an HTML tag.
+    Html,
+    /// The `path` argument of a shortcode, relative to the content directory.
+    Shortcode,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Kind::Link => "link",
+            Kind::Image => "image",
+            Kind::Html => "HTML reference",
+            Kind::Shortcode => "shortcode path",
+        })
+    }
+}
+
+/// A reference to a local file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// 1-based line of the page.
+    pub line: usize,
+    pub kind: Kind,
+    /// The target without a fragment or query.
+    pub target: String,
+}
+
+/// Whether the target points outside of the website, e.g. `https://...` or `mailto:...`.
+fn is_external(target: &str) -> bool {
+    match target.split_once(':') {
+        Some((scheme, _)) => scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)),
+        None => target.starts_with("//"),
+    }
+}
+
+fn push(references: &mut Vec<Reference>, line: usize, kind: Kind, target: &str) {
+    let target = target.split(['#', '?']).next().unwrap();
+    if target.is_empty() || is_external(target) {
+        return;
+    }
+    references.push(Reference {
+        line,
+        kind,
+        target: target.to_owned(),
+    });
+}
+
+/// Removes code spans, so that e.g. `` `v[i](x)` `` isn't taken for a link.
+fn strip_code_spans(line: &str) -> String {
+    line.split('`').step_by(2).collect::<Vec<_>>().join("")
+}
+
+/// The destination of an inline link, i.e. what follows `](`.
+fn link_destination(rest: &str) -> Option<&str> {
+    if let Some(rest) = rest.strip_prefix('<') {
+        return rest.split_once('>').map(|(target, _)| target);
+    }
+    let end = rest.find(')')?;
+    rest[..end].split_whitespace().next()
+}
+
+/// Values of `key="value"` arguments or attributes.
+fn quoted_values<'a>(text: &'a str, key: &str) -> Vec<&'a str> {
+    let pattern = format!("{key}=\"");
+    let mut values = Vec::new();
+    for (i, _) in text.match_indices(&pattern) {
+        // Don't take `data-src="..."` for `src="..."`.
+        let before = text[..i].chars().next_back();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            continue;
+        }
+        let value = &text[i + pattern.len()..];
+        if let Some(end) = value.find('"') {
+            values.push(&value[..end]);
+        }
+    }
+    values
+}
+
+pub fn extract(markdown: &str) -> Vec<Reference> {
+    let mut references = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (i, line) in markdown.lines().enumerate() {
+        let number = i + 1;
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+            continue;
+        }
+
+        // Shortcodes are expanded before Markdown is rendered, even inside code spans.
+        for call in ["{{", "{%"]
+            .iter()
+            .flat_map(|open| line.split(open).skip(1))
+        {
+            for path in quoted_values(call, "path") {
+                references.push(Reference {
+                    line: number,
+                    kind: Kind::Shortcode,
+                    target: path.to_owned(),
+                });
+            }
+        }
+
+        let line = strip_code_spans(line);
+
+        // A reference definition: `[name]: target`.
+        if let Some((label, rest)) = line.trim_start().split_once("]:") {
+            if label.starts_with('[') && !label.contains(']') {
+                let rest = rest.trim_start();
+                let target = match rest.strip_prefix('<') {
+                    Some(rest) => rest.split_once('>').map(|(target, _)| target),
+                    None => rest.split_whitespace().next(),
+                };
+                if let Some(target) = target {
+                    push(&mut references, number, Kind::Link, target);
+                }
+            }
+        }
+
+        for (start, _) in line.match_indices("](") {
+            let Some(target) = link_destination(&line[start + 2..]) else {
+                continue;
+            };
+            let Some(open) = line[..start].rfind('[') else {
+                continue;
+            };
+            let kind = match line[..open].ends_with('!') {
+                true => Kind::Image,
+                false => Kind::Link,
+            };
+            push(&mut references, number, kind, target);
+        }
+
+        for key in ["src", "href"] {
+            for target in quoted_values(&line, key) {
+                push(&mut references, number, Kind::Html, target);
+            }
+        }
+    }
+
+    references
+}
+
+/// Removes `.` and `..` components without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// How Zola turns a file name into a part of the URL.
+fn slugify(name: &str) -> String {
+    name.to_lowercase().replace('_', "-")
+}
+
+/// Follows a URL path like `/lessons/project-showcase/game.png` through the directories
+/// it was generated from.
+fn resolve_url(dir: &Path, url: &str) -> Option<PathBuf> {
+    let mut path = dir.to_path_buf();
+    for part in url.split('/').filter(|part| !part.is_empty()) {
+        let exact = path.join(part);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+        path = fs::read_dir(&path)
+            .ok()?
+            .map(|entry| entry.unwrap().path())
+            .find(|entry| slugify(&entry.file_name().unwrap().to_string_lossy()) == part)?;
+    }
+    Some(path)
+}
+
+/// Finds the file the reference from `page` points to, if it exists.
+/// Absolute URLs are looked up in `static_dir`, then in `content_dir`.
+pub fn resolve(
+    content_dir: &Path,
+    static_dir: &Path,
+    page: &Path,
+    reference: &Reference,
+) -> Option<PathBuf> {
+    let target = reference.target.as_str();
+    let path = if reference.kind == Kind::Shortcode {
+        content_dir.join(target)
+    } else if let Some(internal) = target.strip_prefix("@/") {
+        content_dir.join(internal)
+    } else if target.starts_with('/') {
+        return resolve_url(static_dir, target).or_else(|| resolve_url(content_dir, target));
+    } else {
+        page.parent().unwrap().join(target)
+    };
+    let path = normalize(&path);
+    path.exists().then_some(path)
+}
@@ -0,0 +1,104 @@
+//! Lists and runs the lesson examples.
+//!
+//! The examples are run as the binaries Cargo builds next to this one, so build them first.
+//!
+//! ```text
+//! cargo build --bins                           # build the examples
+//! cargo run --bin lessons                      # list all examples
+//! cargo run --bin lessons -- run 05_generics   # run the chosen examples
+//! cargo run --bin lessons -- run-all           # run everything and report panics and failures
+//! ```
+
+use lessons::{Example, EXAMPLES};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: lessons [list | run <example>... | run-all]";
+
+fn list() {
+    let mut lesson = "";
+    for example in EXAMPLES {
+        if example.lesson() != lesson {
+            lesson = example.lesson();
+            println!("{lesson}");
+        }
+        println!("    {:<28} {}", example.name, example.path());
+    }
+}
+
+/// The names of the examples that didn't run successfully.
+#[derive(Default)]
+struct Problems<'a> {
+    panicked: Vec<&'a str>,
+    /// Exited with an error, were killed or couldn't be started at all.
+    failed: Vec<&'a str>,
+}
+
+/// Runs the examples one by one, printing their output.
+fn run<'a>(examples: impl IntoIterator<Item = &'a Example>) -> Problems<'a> {
+    let mut problems = Problems::default();
+    for example in examples {
+        println!("=== {} ({})", example.name, example.path());
+        match lessons::run(example) {
+            Ok(run) => {
+                print!("{}", run.output);
+                if run.panicked() {
+                    println!("=== {} panicked", example.name);
+                    problems.panicked.push(example.name);
+                } else if !run.status.success() {
+                    println!("=== {} failed: {}", example.name, run.status);
+                    problems.failed.push(example.name);
+                }
+            }
+            Err(error) => {
+                println!("=== failed to run {}: {error}", example.name);
+                problems.failed.push(example.name);
+            }
+        }
+        println!();
+    }
+    problems
+}
+
+fn report(problems: &Problems, total: usize) -> ExitCode {
+    let successful = total - problems.panicked.len() - problems.failed.len();
+    println!("{successful} of {total} examples ran successfully.");
+    if !problems.panicked.is_empty() {
+        println!("Panicked: {}", problems.panicked.join(", "));
+    }
+    if !problems.failed.is_empty() {
+        println!("Failed: {}", problems.failed.join(", "));
+    }
+    if successful == total {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None | Some("list") => {
+            list();
+            ExitCode::SUCCESS
+        }
+        Some("run-all") => report(&run(EXAMPLES), EXAMPLES.len()),
+        Some("run") if args.len() > 1 => {
+            let mut examples = Vec::new();
+            for name in &args[1..] {
+                match lessons::find(name) {
+                    Some(example) => examples.push(example),
+                    None => {
+                        eprintln!("No such example: {name}. See `lessons list`.");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+            report(&run(examples), args.len() - 1)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,466 @@
+//! The table of contents (`content/lessons/table_of_contents.md`), generated from the front matter
+//! of the lesson pages and the list of concepts each lesson introduces
+//! (`content/lessons/concepts.toml`):
+//!
+//! ```toml
+//! [[lesson]]
+//! dir = "05_types_reasoning"
+//! title = "Reasoning About Types"
+//! concepts = [
+//!     "traits",
+//!     "generics",
+//! ]
+//! ```
+//!
+//! The lessons are numbered by their directory and ordered like on the website, by weight.
+//! Each entry repeats the title of its lesson, so that moving or renaming a lesson
+//! can't silently attach its concepts to a different one.
+//!
+//! Only the subset of TOML used by these files is understood: tables, arrays of tables,
+//! and `key = value` pairs whose value is a string, an integer, a date or an array of strings.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+const FRONT_MATTER_DELIMITER: &str = "+++";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    /// A `YYYY-MM-DD` date.
+    Date(String),
+    Array(Vec<String>),
+}
+
+/// A parsing error. The line number is 1-based.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Error {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Error {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A table of `key = value` pairs, remembering the line of each key.
+#[derive(Debug, Default)]
+pub struct Table {
+    /// The 1-based line of the table's header, or of the document's first line for the root table.
+    pub line: usize,
+    entries: BTreeMap<String, (usize, Value)>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    fn required(&self, key: &str) -> Result<&Value, Error> {
+        self.get(key)
+            .ok_or_else(|| Error::new(self.line, format!("missing `{key}`")))
+    }
+
+    pub fn string(&self, key: &str) -> Result<&str, Error> {
+        match self.required(key)? {
+            Value::String(string) => Ok(string),
+            _ => Err(self.type_error(key, "a string")),
+        }
+    }
+
+    pub fn integer(&self, key: &str) -> Result<i64, Error> {
+        match self.required(key)? {
+            Value::Integer(integer) => Ok(*integer),
+            _ => Err(self.type_error(key, "an integer")),
+        }
+    }
+
+    /// A date that exists in the calendar, unlike e.g. `2025-02-30`.
+    pub fn date(&self, key: &str) -> Result<&str, Error> {
+        match self.required(key)? {
+            Value::Date(date) if is_valid_date(date) => Ok(date),
+            _ => Err(self.type_error(key, "a valid date")),
+        }
+    }
+
+    pub fn array(&self, key: &str) -> Result<&[String], Error> {
+        match self.required(key)? {
+            Value::Array(array) => Ok(array),
+            _ => Err(self.type_error(key, "an array of strings")),
+        }
+    }
+
+    fn type_error(&self, key: &str, expected: &str) -> Error {
+        Error::new(self.entries[key].0, format!("`{key}` should be {expected}"))
+    }
+}
+
+/// A parsed TOML document.
+#[derive(Debug, Default)]
+pub struct Document {
+    pub root: Table,
+    /// `[name]` tables.
+    pub tables: BTreeMap<String, Table>,
+    /// `[[name]]` arrays of tables.
+    pub arrays: BTreeMap<String, Vec<Table>>,
+}
+
+/// Parses a quoted string at the start of `text`, returning it and the rest of `text`.
+fn parse_string(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.strip_prefix('"')?.char_indices();
+    let mut string = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((string, &text[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                '"' => string.push('"'),
+                '\\' => string.push('\\'),
+                'n' => string.push('\n'),
+                _ => return None,
+            },
+            c => string.push(c),
+        }
+    }
+    None
+}
+
+fn is_date(text: &str) -> bool {
+    let parts: Vec<&str> = text.split('-').collect();
+    matches!(parts.as_slice(), [year, month, day]
+        if year.len() == 4 && month.len() == 2 && day.len() == 2
+            && parts.iter().all(|part| part.bytes().all(|b| b.is_ascii_digit())))
+}
+
+/// Checks the month and the day of a `YYYY-MM-DD` date.
+fn is_valid_date(date: &str) -> bool {
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().unwrap_or(0));
+    let (year, month, day) = (parts.next(), parts.next(), parts.next());
+    let (Some(year), Some(month), Some(day)) = (year, month, day) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => 0,
+    };
+    (1..=days).contains(&day)
+}
+
+/// Removes a trailing `# comment`, unless it's inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if in_string => {
+                escaped = !escaped;
+                continue;
+            }
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn parse_array(mut text: &str, line: usize) -> Result<Vec<String>, Error> {
+    let error = || Error::new(line, "expected an array of strings");
+    let mut array = Vec::new();
+    text = text.strip_prefix('[').ok_or_else(error)?.trim_start();
+    loop {
+        if let Some(rest) = text.strip_prefix(']') {
+            return match rest.trim() {
+                "" => Ok(array),
+                _ => Err(error()),
+            };
+        }
+        let (string, rest) = parse_string(text).ok_or_else(error)?;
+        array.push(string);
+        text = rest.trim_start();
+        match text.strip_prefix(',') {
+            Some(rest) => text = rest.trim_start(),
+            None if text.starts_with(']') => {}
+            None => return Err(error()),
+        }
+    }
+}
+
+fn parse_value(text: &str, line: usize) -> Result<Value, Error> {
+    if text.starts_with('"') {
+        match parse_string(text) {
+            Some((string, rest)) if rest.trim().is_empty() => Ok(Value::String(string)),
+            _ => Err(Error::new(line, "malformed string")),
+        }
+    } else if text.starts_with('[') {
+        parse_array(text, line).map(Value::Array)
+    } else if is_date(text) {
+        Ok(Value::Date(text.to_owned()))
+    } else {
+        text.parse()
+            .map(Value::Integer)
+            .map_err(|_| Error::new(line, format!("unsupported value `{text}`")))
+    }
+}
+
+/// Parses `text`, whose first line is line number `first_line`.
+pub fn parse_toml(text: &str, first_line: usize) -> Result<Document, Error> {
+    let mut document = Document::default();
+    document.root.line = first_line;
+    let mut current: Option<(bool, String)> = None;
+    let mut lines = text.lines().zip(first_line..);
+
+    while let Some((line, number)) = lines.next() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+            let table = Table {
+                line: number,
+                ..Table::default()
+            };
+            document
+                .arrays
+                .entry(name.trim().to_owned())
+                .or_default()
+                .push(table);
+            current = Some((true, name.trim().to_owned()));
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let table = Table {
+                line: number,
+                ..Table::default()
+            };
+            if document
+                .tables
+                .insert(name.trim().to_owned(), table)
+                .is_some()
+            {
+                return Err(Error::new(number, format!("table `{name}` defined twice")));
+            }
+            current = Some((false, name.trim().to_owned()));
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Error::new(number, "expected `key = value`"));
+        };
+        let (key, mut value) = (key.trim(), value.trim().to_owned());
+        // Arrays may span multiple lines.
+        if value.starts_with('[') {
+            while !strip_comment(&value).trim_end().ends_with(']') {
+                let Some((line, _)) = lines.next() else {
+                    return Err(Error::new(number, "unterminated array"));
+                };
+                value.push(' ');
+                value.push_str(strip_comment(line).trim());
+            }
+        }
+        let value = parse_value(strip_comment(&value).trim(), number)?;
+
+        let table = match &current {
+            None => &mut document.root,
+            Some((false, name)) => document.tables.get_mut(name).unwrap(),
+            Some((true, name)) => document.arrays.get_mut(name).unwrap().last_mut().unwrap(),
+        };
+        if table.entries.contains_key(key) {
+            return Err(Error::new(number, format!("`{key}` defined twice")));
+        }
+        table.entries.insert(key.to_owned(), (number, value));
+    }
+
+    Ok(document)
+}
+
+/// Splits a page into its front matter (without the delimiters) and the rest.
+pub fn split_front_matter(page: &str) -> Result<(&str, &str), Error> {
+    let rest = page
+        .strip_prefix(FRONT_MATTER_DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n'))
+        .ok_or_else(|| Error::new(1, "expected front matter delimited by `+++`"))?;
+    let end = rest
+        .find(&format!("\n{FRONT_MATTER_DELIMITER}\n"))
+        .ok_or_else(|| Error::new(1, "unterminated front matter"))?;
+    Ok((
+        &rest[..end + 1],
+        &rest[end + FRONT_MATTER_DELIMITER.len() + 2..],
+    ))
+}
+
+/// What the table of contents needs to know about a lesson page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lesson {
+    /// The lesson's directory, e.g. `05_types_reasoning`.
+    pub dir: String,
+    /// The number in front of the directory name.
+    pub number: u32,
+    pub title: String,
+    pub weight: i64,
+    pub lesson_date: String,
+}
+
+/// The number of a lesson directory, e.g. 5 for `05_types_reasoning`.
+/// Directories without one, like `project_showcase`, aren't lessons.
+pub fn lesson_number(dir: &str) -> Option<u32> {
+    let (number, name) = dir.split_once('_')?;
+    if name.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+/// Reads the lesson from its `index.md`.
+pub fn parse_lesson(dir: &str, page: &str) -> Result<Lesson, Error> {
+    let number =
+        lesson_number(dir).ok_or_else(|| Error::new(1, format!("`{dir}` is not numbered")))?;
+    let (front_matter, _) = split_front_matter(page)?;
+    // The front matter starts at the second line of the page.
+    let document = parse_toml(front_matter, 2)?;
+    let title = document.root.string("title")?.to_owned();
+    let weight = document.root.integer("weight")?;
+    let extra = document
+        .tables
+        .get("extra")
+        .ok_or_else(|| Error::new(1, "missing `[extra]`"))?;
+
+    Ok(Lesson {
+        dir: dir.to_owned(),
+        number,
+        title,
+        weight,
+        lesson_date: extra.date("lesson_date")?.to_owned(),
+    })
+}
+
+/// An entry of the concept list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Concepts {
+    /// The 1-based line of the entry's `[[lesson]]` header.
+    pub line: usize,
+    pub dir: String,
+    pub title: String,
+    pub concepts: Vec<String>,
+}
+
+pub fn parse_concepts(text: &str) -> Result<Vec<Concepts>, Error> {
+    let document = parse_toml(text, 1)?;
+    if let Some(key) = document.root.entries.keys().next() {
+        return Err(Error::new(
+            1,
+            format!("unexpected `{key}` outside of `[[lesson]]`"),
+        ));
+    }
+    if let Some(name) = document
+        .tables
+        .keys()
+        .chain(document.arrays.keys())
+        .find(|n| *n != "lesson")
+    {
+        return Err(Error::new(1, format!("unexpected table `{name}`")));
+    }
+
+    let tables = document.arrays.get("lesson").map_or(&[][..], Vec::as_slice);
+    tables
+        .iter()
+        .map(|table| {
+            Ok(Concepts {
+                line: table.line,
+                dir: table.string("dir")?.to_owned(),
+                title: table.string("title")?.to_owned(),
+                concepts: table.array("concepts")?.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// The order of the lessons on the website.
+pub fn sort_lessons(lessons: &mut [Lesson]) {
+    lessons.sort_by(|a, b| (a.weight, &a.dir).cmp(&(b.weight, &b.dir)));
+}
+
+/// Checks that the concept list describes exactly the given (sorted) lessons, in the same order.
+/// Returns a description of every disagreement.
+pub fn check_concepts(lessons: &[Lesson], concepts: &[Concepts]) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    for lesson in lessons {
+        if !concepts.iter().any(|entry| entry.dir == lesson.dir) {
+            errors.push(format!("lesson `{}` has no concepts listed", lesson.dir));
+        }
+    }
+    for (i, entry) in concepts.iter().enumerate() {
+        let line = entry.line;
+        if concepts[..i].iter().any(|other| other.dir == entry.dir) {
+            errors.push(format!("line {line}: `{}` is listed twice", entry.dir));
+            continue;
+        }
+        let Some(lesson) = lessons.iter().find(|lesson| lesson.dir == entry.dir) else {
+            errors.push(format!("line {line}: there is no lesson `{}`", entry.dir));
+            continue;
+        };
+        if entry.title != lesson.title {
+            errors.push(format!(
+                "line {line}: `{}` is titled \"{}\", not \"{}\"",
+                entry.dir, lesson.title, entry.title
+            ));
+        }
+        if entry.concepts.is_empty() {
+            errors.push(format!("line {line}: `{}` lists no concepts", entry.dir));
+        }
+    }
+
+    if errors.is_empty() {
+        let listed: Vec<&str> = concepts.iter().map(|entry| entry.dir.as_str()).collect();
+        let expected: Vec<&str> = lessons.iter().map(|lesson| lesson.dir.as_str()).collect();
+        if listed != expected {
+            errors.push(format!(
+                "the lessons should be listed in the order of the website: {}",
+                expected.join(", ")
+            ));
+        }
+    }
+
+    errors
+}
+
+/// The body of the table of contents. Expects the concept list to agree with the lessons.
+pub fn render(lessons: &[Lesson], concepts: &[Concepts]) -> String {
+    let mut body = String::from("\n# Lessons have the following content & introduced concepts:\n");
+    for lesson in lessons {
+        let entry = concepts
+            .iter()
+            .find(|entry| entry.dir == lesson.dir)
+            .expect("the concept list should agree with the lessons");
+        let heading = format!("{}. ", lesson.number);
+        body.push_str(&format!("\n{heading}{}\n", lesson.title));
+        for concept in &entry.concepts {
+            body.push_str(&format!(
+                "{:indent$}- {concept}\n",
+                "",
+                indent = heading.len()
+            ));
+        }
+    }
+    body
+}
@@ -0,0 +1,64 @@
+use lessons::anchors::{self, Error};
+use std::process::Command;
+
+#[test]
+fn regions_are_extracted_without_markers() {
+    let source = "\
+// ANCHOR: all
+struct Foo;
+
+impl Foo {
+    // ANCHOR: method
+    fn bar(&self) {}
+    // ANCHOR_END: method
+}
+// ANCHOR_END: all
+";
+    let anchored = anchors::parse(source).unwrap();
+
+    assert_eq!(
+        anchored.stripped,
+        "struct Foo;\n\nimpl Foo {\n    fn bar(&self) {}\n}\n"
+    );
+    assert_eq!(anchored.regions["all"], anchored.stripped);
+    // Common indentation is removed.
+    assert_eq!(anchored.regions["method"], "fn bar(&self) {}\n");
+}
+
+#[test]
+fn malformed_markers_are_rejected() {
+    assert_eq!(
+        anchors::parse("// ANCHOR: a\n// ANCHOR: a\n"),
+        Err(Error::Duplicate {
+            name: "a".to_owned(),
+            line: 2
+        })
+    );
+    assert_eq!(
+        anchors::parse("fn main() {}\n// ANCHOR_END: a\n"),
+        Err(Error::Unopened {
+            name: "a".to_owned(),
+            line: 2
+        })
+    );
+    assert_eq!(
+        anchors::parse("// ANCHOR: a\nfn main() {}\n"),
+        Err(Error::Unclosed {
+            name: "a".to_owned(),
+            line: 1
+        })
+    );
+}
+
+#[test]
+fn generated_files_are_up_to_date() {
+    let output = Command::new(env!("CARGO_BIN_EXE_anchors"))
+        .arg("--check")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,134 @@
+//! Checks that the examples from the previous semesters don't silently drop out of the build.
+//!
+//! Every `.rs` file under `content/lessons/old/<semester>` has to be either registered
+//! as a `[[bin]]` in that semester's `Cargo.toml` (so it's built and tested with the workspace),
+//! or listed in `package.metadata.lessons.expected-failures`, in which case it has to
+//! still fail to compile.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+const ARCHIVE_DIR: &str = "content/lessons/old";
+
+/// The relevant parts of a semester's manifest.
+struct Manifest {
+    bin_paths: BTreeSet<String>,
+    expected_failures: BTreeSet<String>,
+}
+
+fn quoted_strings(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split('"').skip(1).step_by(2).map(str::to_owned)
+}
+
+fn parse_manifest(manifest: &str) -> Manifest {
+    let mut bin_paths = BTreeSet::new();
+    let mut expected_failures = BTreeSet::new();
+    let mut in_bin = false;
+    let mut lines = manifest.lines().map(str::trim);
+
+    while let Some(line) = lines.next() {
+        if line.starts_with('[') {
+            in_bin = line == "[[bin]]";
+        } else if let Some(value) = line.strip_prefix("path = ") {
+            if in_bin {
+                bin_paths.extend(quoted_strings(value));
+            }
+        } else if let Some(value) = line.strip_prefix("expected-failures = ") {
+            let mut array = value.to_owned();
+            while !array.contains(']') {
+                array.push_str(lines.next().expect("unterminated expected-failures"));
+            }
+            expected_failures.extend(quoted_strings(&array));
+        }
+    }
+
+    Manifest {
+        bin_paths,
+        expected_failures,
+    }
+}
+
+fn collect_rs_files(dir: &Path, base: &Path, files: &mut BTreeSet<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_rs_files(&path, base, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            let relative = path.strip_prefix(base).unwrap();
+            files.insert(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+fn semesters() -> Vec<PathBuf> {
+    let archive = Path::new(env!("CARGO_MANIFEST_DIR")).join(ARCHIVE_DIR);
+    let mut semesters: Vec<PathBuf> = fs::read_dir(archive)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    semesters.sort();
+    semesters
+}
+
+#[test]
+fn every_archived_example_is_registered() {
+    let mut failures = Vec::new();
+
+    for semester in semesters() {
+        let name = semester.file_name().unwrap().to_string_lossy().into_owned();
+        let Ok(manifest) = fs::read_to_string(semester.join("Cargo.toml")) else {
+            failures.push(format!("{name}: missing Cargo.toml"));
+            continue;
+        };
+        let manifest = parse_manifest(&manifest);
+
+        let mut files = BTreeSet::new();
+        collect_rs_files(&semester, &semester, &mut files);
+
+        for file in &files {
+            let registered = manifest.bin_paths.contains(file);
+            let expected_failure = manifest.expected_failures.contains(file);
+            match (registered, expected_failure) {
+                (false, false) => failures.push(format!("{name}/{file} is not registered")),
+                (true, true) => failures.push(format!(
+                    "{name}/{file} is both a binary and an expected failure"
+                )),
+                _ => {}
+            }
+        }
+        for file in manifest.bin_paths.union(&manifest.expected_failures) {
+            if !files.contains(file) {
+                failures.push(format!("{name}/{file} is registered, but doesn't exist"));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn expected_failures_still_fail_to_compile() {
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("archive");
+    fs::create_dir_all(&work_dir).unwrap();
+    let mut failures = Vec::new();
+
+    for semester in semesters() {
+        let Ok(manifest) = fs::read_to_string(semester.join("Cargo.toml")) else {
+            continue;
+        };
+        for file in parse_manifest(&manifest).expected_failures {
+            if common::check(&semester.join(&file), &work_dir).is_ok() {
+                let name = semester.file_name().unwrap().to_string_lossy();
+                failures.push(format!(
+                    "{name}/{file} compiles now, register it as a [[bin]] instead"
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,43 @@
+//! Helpers shared by the integration tests that compile code with rustc directly.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn rustc(source_path: &Path) -> Command {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    let mut command = Command::new(rustc);
+    command
+        .args(["--edition", "2021", "--crate-type", "bin"])
+        .arg(source_path);
+    command
+}
+
+fn result(command: &mut Command) -> Result<(), String> {
+    let output = command.output().expect("failed to run rustc");
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// Type- and borrow-checks a single file, returning rustc's diagnostics if it fails.
+pub fn check(source_path: &Path, out_dir: &Path) -> Result<(), String> {
+    result(
+        rustc(source_path)
+            .args(["--emit", "metadata", "--out-dir"])
+            .arg(out_dir),
+    )
+}
+
+/// Compiles a single file into an executable, returning rustc's diagnostics if it fails.
+pub fn build(source_path: &Path, out_dir: &Path) -> Result<PathBuf, String> {
+    let exe = out_dir.join(format!(
+        "{}{}",
+        source_path.file_stem().unwrap().to_string_lossy(),
+        std::env::consts::EXE_SUFFIX
+    ));
+    result(rustc(source_path).arg("-o").arg(&exe)).map(|()| exe)
+}
@@ -0,0 +1,189 @@
+//! Checks the "won't compile" snippets kept in comments of the lesson examples.
+//!
+//! A snippet is marked by a comment mentioning the expected error code in parentheses,
+//! e.g. `(E0308)`. Errors without a code are given by their message instead,
+//! e.g. `(error: this operation will panic at runtime)`. There are two forms:
+//!
+//! - a single commented-out line with the marker in a trailing comment:
+//!
+//!   ```text
+//!   // arr3[0]; // won't compile (E0608): cannot index into a value of type ...
+//!   ```
+//!
+//! - a standalone marker comment followed by a block of commented-out lines,
+//!   which ends at the first line that is not a comment:
+//!
+//!   ```text
+//!   // This doesn't compile (E0597) - incorrect lifetimes
+//!   //
+//!   // let string1 = String::from("long string is long");
+//!   // ...
+//!   ```
+//!
+//! For every snippet, the host file is compiled with only that snippet uncommented,
+//! and rustc has to reject it with the expected error.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+const LESSONS_DIR: &str = "content/lessons";
+
+struct Snippet {
+    /// 0-based indices of the lines to uncomment.
+    lines: Vec<usize>,
+    /// How the expected error starts in rustc's output, e.g. `error[E0308]`.
+    expected: String,
+}
+
+/// Finds an error code written as `(E1234)`, or an error message written as `(error: ...)`.
+fn find_expected_error(comment: &str) -> Option<String> {
+    if let Some(start) = comment.find("(error: ") {
+        let message = &comment[start + 1..];
+        return message.find(')').map(|end| message[..end].to_owned());
+    }
+    comment.match_indices("(E").find_map(|(i, _)| {
+        let code = comment.get(i + 1..i + 6)?;
+        let closed = comment[i + 6..].starts_with(')');
+        (closed && code[1..].bytes().all(|b| b.is_ascii_digit())).then(|| format!("error[{code}]"))
+    })
+}
+
+fn comment_body(line: &str) -> Option<&str> {
+    line.trim_start().strip_prefix("//")
+}
+
+fn find_snippets(file: &Path, source: &str) -> Vec<Snippet> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut snippets = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(body) = comment_body(line) else {
+            continue;
+        };
+        // Doc comments (`///`, `//!`) are never markers.
+        if body.starts_with('/') || body.starts_with('!') {
+            continue;
+        }
+        let Some(expected) = find_expected_error(body) else {
+            continue;
+        };
+
+        let snippet_lines = match body.find("//") {
+            // A commented-out line of code with a trailing marker.
+            Some(_) => vec![i],
+            // A standalone marker followed by a commented-out block.
+            None => (i + 1..lines.len())
+                .take_while(|&j| comment_body(lines[j]).is_some())
+                .collect(),
+        };
+        assert!(
+            !snippet_lines.is_empty(),
+            "{}:{}: marker is not followed by a commented-out snippet",
+            file.display(),
+            i + 1
+        );
+
+        snippets.push(Snippet {
+            lines: snippet_lines,
+            expected,
+        });
+    }
+
+    snippets
+}
+
+fn uncomment(line: &str) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let body = comment_body(line).unwrap();
+    let body = body.strip_prefix(' ').unwrap_or(body);
+    format!("{}{}", &line[..indent], body)
+}
+
+fn make_variant(source: &str, snippet: &Snippet) -> String {
+    source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if snippet.lines.contains(&i) {
+                uncomment(line)
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collect_rs_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_rs_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn commented_out_snippets_fail_with_expected_errors() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_fail");
+    fs::create_dir_all(&work_dir).unwrap();
+
+    let mut files = Vec::new();
+    collect_rs_files(&root.join(LESSONS_DIR), &mut files);
+    files.sort();
+
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    for file in files {
+        let source = fs::read_to_string(&file).unwrap();
+        let snippets = find_snippets(&file, &source);
+        if snippets.is_empty() {
+            continue;
+        }
+        let name = file.strip_prefix(root).unwrap().display().to_string();
+
+        // Errors in a variant are meaningful only if the host file itself compiles.
+        if let Err(stderr) = common::check(&file, &work_dir) {
+            failures.push(format!(
+                "{name}: the file itself does not compile:\n{stderr}"
+            ));
+            continue;
+        }
+
+        for (n, snippet) in snippets.iter().enumerate() {
+            let variant_path = work_dir.join(format!(
+                "{}_{}.rs",
+                file.file_stem().unwrap().to_string_lossy(),
+                n
+            ));
+            fs::write(&variant_path, make_variant(&source, snippet)).unwrap();
+
+            let line = snippet.lines[0] + 1;
+            // Some errors, like `this operation will panic at runtime`,
+            // are only reported when generating code.
+            let result = common::check(&variant_path, &work_dir)
+                .and_then(|()| common::build(&variant_path, &work_dir).map(drop));
+            match result {
+                Ok(()) => failures.push(format!(
+                    "{name}:{line}: expected `{}`, but the snippet compiles",
+                    snippet.expected
+                )),
+                Err(stderr) if !stderr.contains(&snippet.expected) => failures.push(format!(
+                    "{name}:{line}: expected `{}`, got:\n{stderr}",
+                    snippet.expected
+                )),
+                Err(_) => {}
+            }
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "no compile-fail snippets found");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
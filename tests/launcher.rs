@@ -0,0 +1,65 @@
+//! Checks the `lessons` launcher against the binaries registered in `Cargo.toml`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Reads the `name` and `path` of every `[[bin]]` target from the manifest.
+fn bins(manifest: &str) -> BTreeSet<(String, String)> {
+    let mut bins = BTreeSet::new();
+    let mut in_bin = false;
+    let mut name = None;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_bin = line == "[[bin]]";
+        } else if let Some(value) = line.strip_prefix("name = ").filter(|_| in_bin) {
+            name = Some(value.trim_matches('"').to_owned());
+        } else if let Some(value) = line.strip_prefix("path = ").filter(|_| in_bin) {
+            bins.insert((name.take().unwrap(), value.trim_matches('"').to_owned()));
+        }
+    }
+    bins
+}
+
+#[test]
+fn every_example_binary_is_in_the_launcher() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+    let mut expected = bins(&manifest);
+    expected.retain(|(_, path)| path.starts_with("content/"));
+    let actual: BTreeSet<(String, String)> = lessons::EXAMPLES
+        .iter()
+        .map(|example| (example.name.to_owned(), example.path().to_owned()))
+        .collect();
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn run_all_reports_panicking_examples() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lessons"))
+        .arg("run-all")
+        .env("RUST_BACKTRACE", "0")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    // The output of the examples is captured and printed, their stderr included.
+    assert!(stdout.contains(
+        "=== 01_hello_world (content/lessons/01_introduction/hello_world.rs)\nHello, World!\n"
+    ));
+    assert!(stdout.contains("not implemented: This is only to make code type-check and compile."));
+    assert!(stdout.contains("=== 05_trait_generic_types panicked\n"));
+    assert!(stdout.ends_with("Panicked: 05_trait_generic_types\n"));
+}
+
+#[test]
+fn examples_are_grouped_by_lesson() {
+    let lesson = |name| lessons::find(name).unwrap().lesson();
+    assert_eq!(lesson("01_hello_world"), "01_introduction");
+    assert_eq!(lesson("02_dont_panic"), "02_ownership");
+    assert_eq!(lesson("02_string_formatting"), "02_ownership");
+}
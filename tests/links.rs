@@ -0,0 +1,87 @@
+use lessons::links::{self, Kind, Reference};
+use std::path::Path;
+use std::process::Command;
+
+fn reference(line: usize, kind: Kind, target: &str) -> Reference {
+    Reference {
+        line,
+        kind,
+        target: target.to_owned(),
+    }
+}
+
+#[test]
+fn references_are_extracted() {
+    let page = "\
+![Clippy](clippy.jpg) and [the slides](dont_panic/dont_panic.pdf \"PDF\").
+See [the docs](https://doc.rust-lang.org/) or [above](#hello-world).
+
+{{ include_code_sample(path=\"lessons/03_data_types/enums.rs\", language=\"rust\") }}
+<iframe src=\"slides.html#/1\" data-src=\"ignored.html\"></iframe>
+[slides]: <slides/module system.html>
+";
+    assert_eq!(
+        links::extract(page),
+        [
+            reference(1, Kind::Image, "clippy.jpg"),
+            reference(1, Kind::Link, "dont_panic/dont_panic.pdf"),
+            reference(4, Kind::Shortcode, "lessons/03_data_types/enums.rs"),
+            reference(5, Kind::Html, "slides.html"),
+            reference(6, Kind::Link, "slides/module system.html"),
+        ]
+    );
+}
+
+#[test]
+fn code_is_skipped() {
+    let page = "\
+Indexing looks like `v[0](x)`, but [this](real.md) is a link.
+
+```rust
+let f = fns[0](1); // [not](a_link.rs)
+```
+";
+    assert_eq!(links::extract(page), [reference(1, Kind::Link, "real.md")]);
+}
+
+#[test]
+fn references_are_resolved() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let content = root.join("content");
+    let static_dir = root.join("static");
+    let page = content.join("lessons/01_introduction/index.md");
+    let resolve =
+        |kind, target| links::resolve(&content, &static_dir, &page, &reference(1, kind, target));
+
+    assert_eq!(
+        resolve(Kind::Image, "clippy.jpg"),
+        Some(content.join("lessons/01_introduction/clippy.jpg"))
+    );
+    assert_eq!(
+        resolve(Kind::Link, "../02_ownership/dont_panic/dont_panic.pdf"),
+        Some(content.join("lessons/02_ownership/dont_panic/dont_panic.pdf"))
+    );
+    assert_eq!(
+        resolve(Kind::Shortcode, "lessons/01_introduction/hello_world.rs"),
+        Some(content.join("lessons/01_introduction/hello_world.rs"))
+    );
+    assert_eq!(
+        resolve(Kind::Html, "/lessons/project-showcase/game.png"),
+        Some(content.join("lessons/project_showcase/game.png"))
+    );
+    assert_eq!(resolve(Kind::Image, "missing.jpg"), None);
+    assert_eq!(
+        resolve(Kind::Shortcode, "01_introduction/hello_world.rs"),
+        None
+    );
+}
+
+#[test]
+fn content_has_no_dangling_references() {
+    let output = Command::new(env!("CARGO_BIN_EXE_links")).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,248 @@
+//! Compiles and runs the ```` ```rust ```` blocks embedded in the lesson pages, like rustdoc does
+//! with doctests.
+//!
+//! A block is compiled as a binary: unless it defines its own `fn main`, it is wrapped in one.
+//! How a block is tested can be changed with an HTML comment above it
+//! (Zola would treat extra words in the fence itself as the block's language):
+//!
+//! ```text
+//! <!-- test: ignore -->                  not compiled at all, e.g. because it's only a fragment
+//! <!-- test: no_run -->                  only compiled, e.g. because it never terminates
+//! <!-- test: should_panic -->            has to panic when run
+//! <!-- test: compile_fail, E0382 -->     has to be rejected by rustc with the given error code
+//! ```
+//!
+//! Like the hidden `# ` lines of doctests, the comment can also contain code that is put
+//! in front of the block, but doesn't show up on the page:
+//!
+//! ```text
+//! <!-- test: compile_fail, E0594
+//! use std::sync::Arc;
+//! -->
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+
+const LESSONS_DIR: &str = "content/lessons";
+const ANNOTATION_PREFIX: &str = "<!-- test:";
+/// How long a block may run before it is considered stuck.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum Mode {
+    #[default]
+    Run,
+    Ignore,
+    NoRun,
+    ShouldPanic,
+    CompileFail(String),
+}
+
+struct Block {
+    /// 1-based line of the opening fence.
+    line: usize,
+    /// The code with the hidden setup from the annotation in front of it.
+    code: String,
+    mode: Mode,
+}
+
+fn parse_mode(tokens: &str) -> Result<Mode, String> {
+    let tokens: Vec<&str> = tokens.split(',').map(str::trim).collect();
+    match tokens.as_slice() {
+        [""] => Ok(Mode::Run),
+        ["ignore"] => Ok(Mode::Ignore),
+        ["no_run"] => Ok(Mode::NoRun),
+        ["should_panic"] => Ok(Mode::ShouldPanic),
+        ["compile_fail", code] if code.starts_with('E') => Ok(Mode::CompileFail(code.to_string())),
+        _ => Err(format!("unknown annotation `{}`", tokens.join(", "))),
+    }
+}
+
+fn extract_blocks(markdown: &str) -> Result<Vec<Block>, String> {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+    let mut annotation: Option<(Mode, String)> = None;
+
+    while let Some((i, line)) = lines.next() {
+        let error = |message: &str| format!("line {}: {message}", i + 1);
+
+        if let Some(rest) = line.trim().strip_prefix(ANNOTATION_PREFIX) {
+            if annotation.is_some() {
+                return Err(error("two annotations in a row"));
+            }
+            let (mode, setup) = match rest.strip_suffix("-->") {
+                Some(mode) => (mode, String::new()),
+                None => {
+                    let mut setup = String::new();
+                    loop {
+                        let Some((_, line)) = lines.next() else {
+                            return Err(error("unterminated annotation"));
+                        };
+                        if line.trim() == "-->" {
+                            break;
+                        }
+                        setup.push_str(line);
+                        setup.push('\n');
+                    }
+                    (rest, setup)
+                }
+            };
+            let mode = parse_mode(mode).map_err(|e| error(&e))?;
+            annotation = Some((mode, setup));
+            continue;
+        }
+
+        let Some(info) = line.trim_start().strip_prefix("```") else {
+            // The annotation applies only to the code block right below it.
+            if annotation.is_some() && !line.trim().is_empty() {
+                return Err(error("expected a code block after the annotation"));
+            }
+            continue;
+        };
+
+        let mut code = String::new();
+        for (_, line) in lines.by_ref() {
+            if line.trim_start().starts_with("```") {
+                break;
+            }
+            code.push_str(line);
+            code.push('\n');
+        }
+
+        let (mode, setup) = annotation.take().unwrap_or_default();
+        if info.split(',').next().unwrap().trim() == "rust" {
+            blocks.push(Block {
+                line: i + 1,
+                code: setup + &code,
+                mode,
+            });
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// Turns a block into a complete program, the same way rustdoc does.
+fn to_program(code: &str) -> String {
+    let (attributes, body): (Vec<&str>, Vec<&str>) = code
+        .lines()
+        .partition(|line| line.trim_start().starts_with("#!["));
+    let mut program = String::from("#![allow(unused)]\n");
+    for attribute in attributes {
+        program.push_str(attribute);
+        program.push('\n');
+    }
+    if code.contains("fn main(") {
+        program.push_str(&body.join("\n"));
+    } else {
+        program.push_str("fn main() {\n");
+        program.push_str(&body.join("\n"));
+        program.push_str("\n}");
+    }
+    program.push('\n');
+    program
+}
+
+/// Runs the executable, returning whether it succeeded and what it printed to stderr.
+fn run(exe: &Path) -> Result<(bool, String), String> {
+    let mut child = Command::new(exe)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let start = Instant::now();
+    while child.try_wait().map_err(|e| e.to_string())?.is_none() {
+        if start.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            return Err(format!(
+                "timed out after {TIMEOUT:?}, mark it with `no_run`"
+            ));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    ))
+}
+
+fn test_block(block: &Block, source_path: &Path, out_dir: &Path) -> Result<(), String> {
+    fs::write(source_path, to_program(&block.code)).unwrap();
+
+    match &block.mode {
+        Mode::Ignore => Ok(()),
+        Mode::NoRun => common::check(source_path, out_dir),
+        Mode::CompileFail(code) => match common::check(source_path, out_dir) {
+            Ok(()) => Err(format!("expected error {code}, but it compiles")),
+            Err(stderr) if !stderr.contains(&format!("error[{code}]")) => {
+                Err(format!("expected error {code}, got:\n{stderr}"))
+            }
+            Err(_) => Ok(()),
+        },
+        Mode::Run | Mode::ShouldPanic => {
+            let exe = common::build(source_path, out_dir)?;
+            let (success, stderr) = run(&exe)?;
+            match (&block.mode, success) {
+                (Mode::Run, false) => Err(format!("failed when run:\n{stderr}")),
+                (Mode::ShouldPanic, true) => Err("expected a panic, but it succeeded".to_owned()),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+fn lesson_pages(root: &Path) -> Vec<PathBuf> {
+    let mut pages: Vec<PathBuf> = fs::read_dir(root.join(LESSONS_DIR))
+        .unwrap()
+        .map(|entry| entry.unwrap().path().join("index.md"))
+        .filter(|page| page.exists())
+        .collect();
+    pages.sort();
+    pages
+}
+
+#[test]
+fn rust_blocks_in_lesson_pages() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("markdown");
+    fs::create_dir_all(&work_dir).unwrap();
+
+    let mut failures = Vec::new();
+    let mut tested = 0;
+
+    for page in lesson_pages(root) {
+        let name = page.strip_prefix(root).unwrap().display().to_string();
+        let lesson = page
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_string_lossy();
+        let blocks = match extract_blocks(&fs::read_to_string(&page).unwrap()) {
+            Ok(blocks) => blocks,
+            Err(error) => {
+                failures.push(format!("{name}: {error}"));
+                continue;
+            }
+        };
+
+        for block in blocks {
+            let source_path = work_dir.join(format!("snippet_{lesson}_{}.rs", block.line));
+            if let Err(error) = test_block(&block, &source_path, &work_dir) {
+                failures.push(format!("{name}:{}: {error}", block.line));
+            }
+            tested += 1;
+        }
+    }
+
+    assert!(tested > 0, "no code blocks found");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,140 @@
+//! Golden-output tests for the lesson binaries registered in `Cargo.toml`.
+//!
+//! Every binary is run from the repository root, and its stdout and stderr
+//! are compared with `tests/snapshots/<bin>.stdout` and `tests/snapshots/<bin>.stderr`.
+//! A missing snapshot file stands for empty output.
+//!
+//! To regenerate the snapshots after changing an example, run:
+//!
+//! ```text
+//! BLESS=1 cargo test --test snapshots
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SNAPSHOTS_DIR: &str = "tests/snapshots";
+
+/// Binaries whose output can't be reproduced, e.g. because it depends on the compiler
+/// version and the target. For these only the exit status is checked.
+const NONDETERMINISTIC: &[&str] = &["03_enum_layout"];
+
+/// Reads the names of all `[[bin]]` targets from the manifest: the lesson examples
+/// and the launcher, whose snapshot is the list of examples.
+/// The website tools in `src/bin` are skipped, they write files.
+fn bin_names(manifest: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut in_bin = false;
+    let mut name = None;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_bin = line == "[[bin]]";
+        } else if let Some(value) = line.strip_prefix("name = ").filter(|_| in_bin) {
+            name = Some(value.trim_matches('"').to_owned());
+        } else if let Some(value) = line.strip_prefix("path = ").filter(|_| in_bin) {
+            let name = name.take().unwrap();
+            if !value.trim_matches('"').starts_with("src/bin/") {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Binaries are built next to the `deps` directory containing this test.
+fn bin_path(name: &str) -> PathBuf {
+    let test_exe = std::env::current_exe().unwrap();
+    let dir = test_exe.parent().unwrap().parent().unwrap();
+    dir.join(format!("{name}{}", std::env::consts::EXE_SUFFIX))
+}
+
+/// Removes the parts of the output that differ between runs and toolchains.
+fn normalize(output: &[u8]) -> String {
+    let output = String::from_utf8_lossy(output);
+    // Since Rust 1.88 panic messages include the thread id: `thread 'main' (1234) panicked`.
+    output
+        .lines()
+        .map(|line| match line.split_once("' (") {
+            Some((thread, rest)) if thread.starts_with("thread '") => match rest.split_once(") ") {
+                Some((id, rest)) if id.bytes().all(|b| b.is_ascii_digit()) => {
+                    format!("{thread}' {rest}")
+                }
+                _ => line.to_owned(),
+            },
+            _ => line.to_owned(),
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+fn check_snapshot(path: &Path, actual: &str, bless: bool, failures: &mut Vec<String>) {
+    let expected = fs::read_to_string(path).unwrap_or_default();
+    if expected == actual {
+        return;
+    }
+    if bless {
+        if actual.is_empty() {
+            fs::remove_file(path).unwrap();
+        } else {
+            fs::write(path, actual).unwrap();
+        }
+    } else {
+        failures.push(format!(
+            "{} differs:\n--- expected\n{expected}--- actual\n{actual}",
+            path.display()
+        ));
+    }
+}
+
+#[test]
+fn lesson_binaries_match_snapshots() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let snapshots = root.join(SNAPSHOTS_DIR);
+    let bless = std::env::var_os("BLESS").is_some();
+    if bless {
+        fs::create_dir_all(&snapshots).unwrap();
+    }
+
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    let names = bin_names(&manifest);
+    assert!(!names.is_empty(), "no [[bin]] targets found in Cargo.toml");
+
+    let mut failures = Vec::new();
+    for name in &names {
+        let output = Command::new(bin_path(name))
+            .current_dir(root)
+            .env("LESSONS_SEED", "2137")
+            .env("RUST_BACKTRACE", "0")
+            .output()
+            .unwrap_or_else(|e| panic!("failed to run {name}: {e}"));
+
+        if NONDETERMINISTIC.contains(&name.as_str()) {
+            if !output.status.success() {
+                failures.push(format!("{name} exited with {}", output.status));
+            }
+            continue;
+        }
+
+        let stdout = normalize(&output.stdout);
+        let stderr = normalize(&output.stderr);
+        check_snapshot(
+            &snapshots.join(format!("{name}.stdout")),
+            &stdout,
+            bless,
+            &mut failures,
+        );
+        check_snapshot(
+            &snapshots.join(format!("{name}.stderr")),
+            &stderr,
+            bless,
+            &mut failures,
+        );
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRun with BLESS=1 to update the snapshots.",
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,192 @@
+use lessons::toc::{self, Concepts, Lesson};
+use std::process::Command;
+
+fn lesson(dir: &str, title: &str, weight: i64) -> Lesson {
+    Lesson {
+        dir: dir.to_owned(),
+        number: toc::lesson_number(dir).unwrap(),
+        title: title.to_owned(),
+        weight,
+        lesson_date: "2025-10-02".to_owned(),
+    }
+}
+
+fn concepts(line: usize, dir: &str, title: &str, concepts: &[&str]) -> Concepts {
+    Concepts {
+        line,
+        dir: dir.to_owned(),
+        title: title.to_owned(),
+        concepts: concepts.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+#[test]
+fn front_matter_is_parsed() {
+    let page = "\
++++
+title = \"Feedback #1\" # shown in the list
+date = 2029-01-01
+weight = 1
+[extra]
+lesson_date = 2022-10-31
++++
+
+## Feedback
+";
+    assert_eq!(
+        toc::parse_lesson("04_feedback_1", page),
+        Ok(lesson("04_feedback_1", "Feedback #1", 1)).map(|lesson| Lesson {
+            lesson_date: "2022-10-31".to_owned(),
+            ..lesson
+        })
+    );
+
+    let error = toc::parse_lesson("04_feedback_1", "+++\ntitle = 4\n+++\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 2: `title` should be a string");
+    let error =
+        toc::parse_lesson("04_feedback_1", "+++\ntitle = \"x\"\nweight = 1\n+++\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1: missing `[extra]`");
+
+    let page = |date: &str| format!("+++\ntitle = \"x\"\nweight = 1\n[extra]\n{date}\n+++\n");
+    let error = toc::parse_lesson("04_feedback_1", &page("")).unwrap_err();
+    assert_eq!(error.to_string(), "line 4: missing `lesson_date`");
+    for date in ["2022-02-29", "2022-13-01", "2022-04-31", "2022-10-00"] {
+        let error = toc::parse_lesson("04_feedback_1", &page(&format!("lesson_date = {date}")))
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5: `lesson_date` should be a valid date",
+            "{date}"
+        );
+    }
+    let leap_day = toc::parse_lesson("04_feedback_1", &page("lesson_date = 2024-02-29"));
+    assert_eq!(leap_day.unwrap().lesson_date, "2024-02-29");
+    assert!(toc::parse_lesson("project_showcase", "+++\n+++\n").is_err());
+}
+
+#[test]
+fn concept_list_is_parsed() {
+    let text = "\
+# A comment.
+[[lesson]]
+dir = \"02_ownership\"
+title = \"Ownership Model\"
+concepts = [
+    \"panicking - because it's everywhere (think `unwrap()`)\",
+    \"RAII\", # a comment
+]
+
+[[lesson]]
+dir = \"03_data_types\"
+title = \"Data Types\"
+concepts = [\"structs\", \"enums\"]
+";
+    assert_eq!(
+        toc::parse_concepts(text),
+        Ok(vec![
+            concepts(
+                2,
+                "02_ownership",
+                "Ownership Model",
+                &[
+                    "panicking - because it's everywhere (think `unwrap()`)",
+                    "RAII"
+                ]
+            ),
+            concepts(10, "03_data_types", "Data Types", &["structs", "enums"]),
+        ])
+    );
+
+    let error = toc::parse_concepts("[[lesson]]\ndir = \"02_ownership\"\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 1: missing `title`");
+    let error = toc::parse_concepts("[[lesson]]\nconcepts = [\"a\"\n").unwrap_err();
+    assert_eq!(error.to_string(), "line 2: unterminated array");
+}
+
+#[test]
+fn disagreements_are_reported() {
+    let mut lessons = vec![
+        lesson("05_types_reasoning", "Reasoning About Types", 1),
+        lesson("04_feedback_1", "Feedback #1", 1),
+        lesson("03_data_types", "Data Types", 1),
+    ];
+    toc::sort_lessons(&mut lessons);
+    let dirs: Vec<&str> = lessons.iter().map(|l| l.dir.as_str()).collect();
+    assert_eq!(
+        dirs,
+        ["03_data_types", "04_feedback_1", "05_types_reasoning"]
+    );
+
+    let list = [
+        concepts(1, "03_data_types", "Data Types", &["structs"]),
+        concepts(5, "04_feedback_1", "Traits", &["traits"]),
+        concepts(9, "06_closures_iterators", "Closures", &["closures"]),
+    ];
+    assert_eq!(
+        toc::check_concepts(&lessons, &list),
+        [
+            "lesson `05_types_reasoning` has no concepts listed",
+            "line 5: `04_feedback_1` is titled \"Feedback #1\", not \"Traits\"",
+            "line 9: there is no lesson `06_closures_iterators`",
+        ]
+    );
+
+    let swapped = [
+        concepts(1, "04_feedback_1", "Feedback #1", &["unwrapping"]),
+        concepts(5, "03_data_types", "Data Types", &["structs"]),
+        concepts(
+            9,
+            "05_types_reasoning",
+            "Reasoning About Types",
+            &["traits"],
+        ),
+    ];
+    assert_eq!(
+        toc::check_concepts(&lessons, &swapped),
+        ["the lessons should be listed in the order of the website: \
+          03_data_types, 04_feedback_1, 05_types_reasoning"]
+    );
+}
+
+#[test]
+fn table_is_rendered_in_lesson_order() {
+    let lessons = [
+        lesson("09_concurrency", "Fearless concurrency", 1),
+        lesson("10_design_patterns", "Design patterns", 1),
+    ];
+    let list = [
+        concepts(1, "09_concurrency", "Fearless concurrency", &["threads"]),
+        concepts(
+            5,
+            "10_design_patterns",
+            "Design patterns",
+            &["serde", "clap"],
+        ),
+    ];
+    assert_eq!(
+        toc::render(&lessons, &list),
+        "
+# Lessons have the following content & introduced concepts:
+
+9. Fearless concurrency
+   - threads
+
+10. Design patterns
+    - serde
+    - clap
+"
+    );
+}
+
+#[test]
+fn table_of_contents_is_up_to_date() {
+    let output = Command::new(env!("CARGO_BIN_EXE_toc"))
+        .arg("--check")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}